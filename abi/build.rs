@@ -10,6 +10,7 @@ fn main() {
         .with_derive_builder(&[
             "reservation.ReservationQuery",
             "reservation.ReservationFilter",
+            "reservation.ResourceSearch",
         ])
         .with_derive_builder_into(
             "reservation.ReservationQuery",
@@ -19,8 +20,13 @@ fn main() {
             "reservation.ReservationFilter",
             &["user_id", "resource_id", "desc", "status"],
         )
-        .with_derive_builder_option("reservation.ReservationFilter", &["cursor"])
-        .with_derive_builder_option("reservation.ReservationQuery", &["start", "end"])
+        .with_derive_builder_into(
+            "reservation.ResourceSearch",
+            &["tags", "resource_type", "min_capacity", "limit"],
+        )
+        .with_derive_builder_option("reservation.ReservationFilter", &["cursor", "as_of"])
+        .with_derive_builder_option("reservation.ResourceSearch", &["start", "end"])
+        .with_derive_builder_option("reservation.ReservationQuery", &["start", "end", "as_of"])
         .with_field_attributes(
             &["page_size"],
             &["#[builder(setter(into), default = \"10\")]"],
@@ -28,6 +34,7 @@ fn main() {
         .with_type_attributes(
             &[
                 "reservation.ReservationFilter",
+                "reservation.ResourceSearch",
                 // "reservation.ReservationQuery",
             ],
             &[r#"#[builder(build_fn(name = "private_build"))]"#],
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
 use crate::Error;
 
@@ -8,6 +8,12 @@ use crate::Error;
 pub struct Config {
     pub db: DbConfig,
     pub server: ServerConfig,
+    #[serde(default)]
+    pub changes: ChangesConfig,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -57,6 +63,134 @@ pub struct ServerConfig {
     pub port: u16,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangesConfig {
+    /// seconds to wait for a consumer group to acknowledge the changes before redelivering them
+    #[serde(default = "default_ack_timeout")]
+    pub ack_timeout: u64,
+    /// changes buffered for each listener, a listener falling further behind is disconnected
+    #[serde(default = "default_listen_buffer")]
+    pub listen_buffer: usize,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+fn default_ack_timeout() -> u64 {
+    30
+}
+
+fn default_listen_buffer() -> usize {
+    1024
+}
+
+impl Default for ChangesConfig {
+    fn default() -> Self {
+        Self {
+            ack_timeout: default_ack_timeout(),
+            listen_buffer: default_listen_buffer(),
+            retention: RetentionConfig::default(),
+        }
+    }
+}
+
+/// retention policy of the change queue, changes not acknowledged by a consumer group are kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// seconds to keep the changes
+    pub max_age: Option<u64>,
+    /// number of the latest changes to keep
+    pub max_rows: Option<i64>,
+    /// seconds after which only the latest change of each reservation is kept
+    pub compact_after: Option<u64>,
+    /// seconds between two runs of the retention job, at least 1
+    #[serde(
+        default = "default_retention_interval",
+        deserialize_with = "deserialize_retention_interval"
+    )]
+    pub interval: u64,
+}
+
+fn default_retention_interval() -> u64 {
+    3600
+}
+
+fn deserialize_retention_interval<'de, D: Deserializer<'de>>(d: D) -> Result<u64, D::Error> {
+    match u64::deserialize(d)? {
+        0 => Err(de::Error::custom(
+            "retention interval should be at least 1 second",
+        )),
+        interval => Ok(interval),
+    }
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            max_rows: None,
+            compact_after: None,
+            interval: default_retention_interval(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_age.is_some() || self.max_rows.is_some() || self.compact_after.is_some()
+    }
+}
+
+/// http endpoint to receive the reservation changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// unique name of the webhook, used to track its delivery offset
+    pub name: String,
+    pub url: String,
+    /// secret to sign the payload with HMAC-SHA256
+    pub secret: String,
+    /// attempts to deliver a change before moving it to the dead letters
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// milliseconds to wait before the first retry, doubled for each retry after
+    #[serde(default = "default_backoff")]
+    pub backoff: u64,
+    /// milliseconds the wait between two retries is capped at
+    #[serde(default = "default_max_backoff")]
+    pub max_backoff: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_backoff() -> u64 {
+    500
+}
+
+fn default_max_backoff() -> u64 {
+    30_000
+}
+
+/// local destination to receive the reservation changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkConfig {
+    /// unique name of the sink, used to track its delivery offset
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: SinkKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SinkKind {
+    /// print the changes as JSON lines to stdout
+    Stdout,
+    /// append the changes as JSON lines to a file
+    File { path: String },
+    /// post each change as JSON to an http endpoint
+    Http { url: String },
+}
+
 impl Config {
     pub fn load(filename: impl AsRef<Path>) -> Result<Self, Error> {
         let config =
@@ -87,8 +221,45 @@ mod tests {
                 server: ServerConfig {
                     host: "localhost".to_string(),
                     port: 50001
-                }
+                },
+                changes: ChangesConfig {
+                    ack_timeout: 10,
+                    listen_buffer: 256,
+                    retention: RetentionConfig {
+                        max_age: Some(604800),
+                        max_rows: None,
+                        compact_after: Some(86400),
+                        interval: 3600
+                    }
+                },
+                webhooks: vec![WebhookConfig {
+                    name: "billing".to_string(),
+                    url: "http://localhost:8080/hooks/reservations".to_string(),
+                    secret: "secret".to_string(),
+                    max_attempts: 5,
+                    backoff: 500,
+                    max_backoff: 30_000
+                }],
+                sinks: vec![
+                    SinkConfig {
+                        name: "console".to_string(),
+                        kind: SinkKind::Stdout
+                    },
+                    SinkConfig {
+                        name: "audit".to_string(),
+                        kind: SinkKind::File {
+                            path: "/tmp/reservation-changes.jsonl".to_string()
+                        }
+                    }
+                ]
             }
         )
     }
+
+    #[test]
+    fn zero_retention_interval_should_be_rejected() {
+        let retention: RetentionConfig = serde_yaml::from_str("interval: 60").unwrap();
+        assert_eq!(retention.interval, 60);
+        assert!(serde_yaml::from_str::<RetentionConfig>("interval: 0").is_err());
+    }
 }
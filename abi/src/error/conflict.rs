@@ -1,18 +1,26 @@
 use std::{collections::HashMap, convert::Infallible, str::FromStr};
 
 use chrono::{DateTime, Utc};
+use prost::Message;
 use regex::Regex;
 
+use crate::{ConflictDetails, Suggestion};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReservationConflictInfo {
-    Parsed(ReservationConflict),
+    Parsed(Box<ReservationConflict>),
     UnParsed(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReservationConflict {
     pub new: ReservationWindow,
+    /// the first of the reservations taking the seats
     pub old: ReservationWindow,
+    /// number of reservations the resource could hold at the same time
+    pub capacity: i32,
+    /// ids of the overlapping reservations taking all the seats, empty if unknown
+    pub taken: Vec<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,12 +30,48 @@ pub struct ReservationWindow {
     pub end: DateTime<Utc>,
 }
 
+impl ReservationConflictInfo {
+    /// details of the conflict to be sent along with the error
+    pub fn details(&self, suggestions: Vec<Suggestion>) -> ConflictDetails {
+        let mut details = ConflictDetails {
+            suggestions,
+            ..Default::default()
+        };
+        if let ReservationConflictInfo::Parsed(conflict) = self {
+            details.resource_id = conflict.new.rid.clone();
+            details.capacity = conflict.capacity;
+            details.taken = conflict.taken.clone();
+        }
+        details
+    }
+
+    /// FAILED_PRECONDITION status with the conflict details
+    pub fn into_status(self, suggestions: Vec<Suggestion>) -> tonic::Status {
+        let details = self.details(suggestions);
+        tonic::Status::with_details(
+            tonic::Code::FailedPrecondition,
+            format!("Conflict reservation: {self:?}"),
+            details.encode_to_vec().into(),
+        )
+    }
+}
+
+impl ConflictDetails {
+    /// decode the details from the status of a conflicting reservation
+    pub fn from_status(status: &tonic::Status) -> Option<Self> {
+        if status.code() != tonic::Code::FailedPrecondition || status.details().is_empty() {
+            return None;
+        }
+        Self::decode(status.details()).ok()
+    }
+}
+
 impl FromStr for ReservationConflictInfo {
     type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(conflict) = s.parse() {
-            Ok(ReservationConflictInfo::Parsed(conflict))
+            Ok(ReservationConflictInfo::Parsed(Box::new(conflict)))
         } else {
             Ok(ReservationConflictInfo::UnParsed(s.to_string()))
         }
@@ -49,6 +93,8 @@ impl TryFrom<ParseInfo> for ReservationConflict {
         Ok(Self {
             new: value.new.try_into()?,
             old: value.old.try_into()?,
+            capacity: value.capacity,
+            taken: value.taken,
         })
     }
 }
@@ -80,6 +126,8 @@ fn parse_datetime(s: &str) -> Result<DateTime<Utc>, ()> {
 struct ParseInfo {
     new: HashMap<String, String>,
     old: HashMap<String, String>,
+    capacity: i32,
+    taken: Vec<i64>,
 }
 
 impl FromStr for ParseInfo {
@@ -101,9 +149,27 @@ impl FromStr for ParseInfo {
         if maps.len() != 2 {
             return Err(());
         }
+
+        // "Taken 2 of capacity 2 by reservations (1, 2).", the exclusion constraint has no seats
+        let re = Regex::new(
+            r#"Taken \d+ of capacity (?P<capacity>\d+) by reservations \((?P<taken>[0-9, ]*)\)"#,
+        )
+        .unwrap();
+        let (capacity, taken) = match re.captures(s) {
+            Some(cap) => {
+                let taken = cap["taken"]
+                    .split(',')
+                    .map(|id| id.trim().parse().map_err(|_| ()))
+                    .collect::<Result<_, _>>()?;
+                (cap["capacity"].parse().map_err(|_| ())?, taken)
+            }
+            None => (1, vec![]),
+        };
         Ok(ParseInfo {
             new: maps[0].take().unwrap(),
             old: maps[1].take().unwrap(),
+            capacity,
+            taken,
         })
     }
 }
@@ -148,6 +214,37 @@ mod test {
         assert_eq!(window.end.to_rfc3339(), "2022-12-30T19:00:00+00:00");
     }
 
+    #[test]
+    fn capacity_conflict_error_message_should_parse() {
+        let msg = format!("{ERR_MSG} Taken 2 of capacity 2 by reservations (3, 7).");
+        let info: ReservationConflictInfo = msg.parse().unwrap();
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => {
+                assert_eq!(conflict.old.rid, "ocean-view-room-713");
+                assert_eq!(conflict.capacity, 2);
+                assert_eq!(conflict.taken, vec![3, 7]);
+            }
+            ReservationConflictInfo::UnParsed(_) => panic!("Should be parsed."),
+        }
+    }
+
+    #[test]
+    fn conflict_details_should_be_sent_in_status() {
+        let msg = format!("{ERR_MSG} Taken 1 of capacity 1 by reservations (3).");
+        let info: ReservationConflictInfo = msg.parse().unwrap();
+        let suggestion = Suggestion {
+            resource_id: "ocean-view-room-713".into(),
+            ..Default::default()
+        };
+        let status = info.into_status(vec![suggestion.clone()]);
+
+        let details = ConflictDetails::from_status(&status).unwrap();
+        assert_eq!(details.resource_id, "ocean-view-room-713");
+        assert_eq!(details.capacity, 1);
+        assert_eq!(details.taken, vec![3]);
+        assert_eq!(details.suggestions, vec![suggestion]);
+    }
+
     #[test]
     fn conflict_error_message_should_parse() {
         let info: ReservationConflictInfo = ERR_MSG.parse().unwrap();
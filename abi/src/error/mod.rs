@@ -23,6 +23,9 @@ pub enum Error {
     #[error("Conflict Reservation")]
     ConflictReservation(ReservationConflictInfo),
 
+    #[error("Conflict Reservations")]
+    ConflictReservations(Vec<ReservationConflictInfo>),
+
     #[error("No reservation found by the given condition")]
     NotFound,
 
@@ -35,6 +38,27 @@ pub enum Error {
     #[error("Invalid resource id: {0}")]
     InvalidResourceId(String),
 
+    #[error("Invalid resource capacity: {0}")]
+    InvalidCapacity(i32),
+
+    #[error("Unknown resource: {0}")]
+    UnknownResource(String),
+
+    #[error("Resource already exists: {0}")]
+    ResourceExists(String),
+
+    #[error("No resource is available for the search")]
+    NoAvailableResource,
+
+    #[error("Resource is retired: {0}")]
+    RetiredResource(String),
+
+    #[error("Resource still has reservations: {0}")]
+    ResourceInUse(String),
+
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(String),
+
     #[error("Invalid page size: {0}")]
     InvalidPageSize(i64),
 
@@ -44,6 +68,27 @@ pub enum Error {
     #[error("Invalid Reservation Status: {0}")]
     InvalidStatus(i32),
 
+    #[error("Invalid Reservation Update Type: {0}")]
+    InvalidUpdateType(i32),
+
+    #[error("Invalid consumer group: {0}")]
+    InvalidConsumerGroup(String),
+
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrenceRule(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Invalid update mask path: {0}")]
+    InvalidUpdateMask(String),
+
+    #[error("Listener fell behind, resume from offset {0}")]
+    ListenLagged(i64),
+
+    #[error("Failed to send the change to the event sink: {0}")]
+    EventSinkError(String),
+
     #[error("unknown error")]
     Unknown,
 }
@@ -54,10 +99,28 @@ impl PartialEq for Error {
             (Self::DbError(_), Self::DbError(_)) => true,
             (Self::InvalidTime, Self::InvalidTime) => true,
             (Self::ConflictReservation(v1), Self::ConflictReservation(v2)) => v1 == v2,
+            (Self::ConflictReservations(v1), Self::ConflictReservations(v2)) => v1 == v2,
             (Self::NotFound, Self::NotFound) => true,
             (Self::InvalidReservationId(v1), Self::InvalidReservationId(v2)) => v1 == v2,
             (Self::InvalidUserId(v1), Self::InvalidUserId(v2)) => v1 == v2,
             (Self::InvalidResourceId(v1), Self::InvalidResourceId(v2)) => v1 == v2,
+            (Self::InvalidCapacity(v1), Self::InvalidCapacity(v2)) => v1 == v2,
+            (Self::UnknownResource(v1), Self::UnknownResource(v2)) => v1 == v2,
+            (Self::ResourceExists(v1), Self::ResourceExists(v2)) => v1 == v2,
+            (Self::NoAvailableResource, Self::NoAvailableResource) => true,
+            (Self::RetiredResource(v1), Self::RetiredResource(v2)) => v1 == v2,
+            (Self::ResourceInUse(v1), Self::ResourceInUse(v2)) => v1 == v2,
+            (Self::InvalidDuration(v1), Self::InvalidDuration(v2)) => v1 == v2,
+            (Self::InvalidPageSize(v1), Self::InvalidPageSize(v2)) => v1 == v2,
+            (Self::InvalidCursor(v1), Self::InvalidCursor(v2)) => v1 == v2,
+            (Self::InvalidStatus(v1), Self::InvalidStatus(v2)) => v1 == v2,
+            (Self::InvalidUpdateType(v1), Self::InvalidUpdateType(v2)) => v1 == v2,
+            (Self::InvalidConsumerGroup(v1), Self::InvalidConsumerGroup(v2)) => v1 == v2,
+            (Self::InvalidRecurrenceRule(v1), Self::InvalidRecurrenceRule(v2)) => v1 == v2,
+            (Self::InvalidTimezone(v1), Self::InvalidTimezone(v2)) => v1 == v2,
+            (Self::InvalidUpdateMask(v1), Self::InvalidUpdateMask(v2)) => v1 == v2,
+            (Self::ListenLagged(v1), Self::ListenLagged(v2)) => v1 == v2,
+            (Self::EventSinkError(v1), Self::EventSinkError(v2)) => v1 == v2,
             (Self::Unknown, Self::Unknown) => true,
             _ => false,
         }
@@ -85,18 +148,35 @@ impl From<sqlx::Error> for Error {
 impl From<Error> for tonic::Status {
     fn from(e: Error) -> Self {
         match e {
-            Error::DbError(_) | Error::ConfigReadError | Error::ConfigParseError => {
-                tonic::Status::internal(e.to_string())
-            }
+            Error::DbError(_)
+            | Error::ConfigReadError
+            | Error::ConfigParseError
+            | Error::EventSinkError(_) => tonic::Status::internal(e.to_string()),
             Error::InvalidTime
             | Error::InvalidReservationId(_)
             | Error::InvalidUserId(_)
             | Error::InvalidResourceId(_)
+            | Error::InvalidCapacity(_)
+            | Error::InvalidDuration(_)
             | Error::InvalidPageSize(_)
             | Error::InvalidCursor(_)
-            | Error::InvalidStatus(_) => tonic::Status::invalid_argument(e.to_string()),
-            Error::ConflictReservation(info) => {
-                tonic::Status::failed_precondition(format!("Conflict reservation: {info:?}"))
+            | Error::InvalidStatus(_)
+            | Error::InvalidUpdateType(_)
+            | Error::InvalidConsumerGroup(_)
+            | Error::InvalidRecurrenceRule(_)
+            | Error::InvalidTimezone(_)
+            | Error::InvalidUpdateMask(_) => tonic::Status::invalid_argument(e.to_string()),
+            Error::ConflictReservation(info) => info.into_status(vec![]),
+            Error::UnknownResource(_) | Error::NoAvailableResource => {
+                tonic::Status::not_found(e.to_string())
+            }
+            Error::ResourceExists(_) => tonic::Status::already_exists(e.to_string()),
+            Error::RetiredResource(_) | Error::ResourceInUse(_) => {
+                tonic::Status::failed_precondition(e.to_string())
+            }
+            Error::ListenLagged(_) => tonic::Status::resource_exhausted(e.to_string()),
+            Error::ConflictReservations(infos) => {
+                tonic::Status::failed_precondition(format!("Conflict reservations: {infos:?}"))
             }
             Error::NotFound => {
                 tonic::Status::not_found("No reservation found by the given condition")
@@ -7,8 +7,14 @@ mod utils;
 pub use config::*;
 pub use error::{Error, ReservationConflict, ReservationConflictInfo, ReservationWindow};
 pub use pb::*;
+pub use types::{
+    Frequency, RecurrenceRule, ReservationChange, ReservationSnapshot, MAX_OCCURRENCES,
+    MAX_SEARCH_LIMIT,
+};
 pub use utils::*;
 
+use serde::{Deserialize, Serialize};
+
 pub type ReservationId = i64;
 pub type UserId = String;
 pub type ResourceId = String;
@@ -36,13 +42,26 @@ pub trait ToSql {
 }
 
 /// database equivalent of the "reservation_status" enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
 #[sqlx(type_name = "reservation_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum RsvpStatus {
     Unknown,
     Pending,
     Confirmed,
     Blocked,
+    Cancelled,
+}
+
+/// database equivalent of the "reservation_update_type" enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "reservation_update_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RsvpUpdateType {
+    Unknown,
+    Create,
+    Update,
+    Delete,
 }
 
 impl Validator for ReservationId {
@@ -1,5 +1,4 @@
 /// Core reservation object. Contains all the information for a reservation
-/// if ListenResponse op is DELETE, only id will be populated
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Reservation {
     /// unique id for the reservation, if put into ReservationRequest, id should be
@@ -24,6 +23,91 @@ pub struct Reservation {
     /// extra note
     #[prost(string, tag = "7")]
     pub note: ::prost::alloc::string::String,
+    /// id of the series the reservation is expanded from, 0 if not recurring
+    #[prost(int64, tag = "8")]
+    pub series_id: i64,
+    /// original start time of the occurrence in the series, kept even if the
+    /// occurrence is rescheduled
+    #[prost(message, optional, tag = "9")]
+    pub recurrence_id: ::core::option::Option<::prost_types::Timestamp>,
+    /// why the reservation is cancelled, empty if it's not cancelled
+    #[prost(string, tag = "10")]
+    pub cancel_reason: ::prost::alloc::string::String,
+    /// when the reservation is cancelled
+    #[prost(message, optional, tag = "11")]
+    pub cancelled_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// who cancelled the reservation
+    #[prost(string, tag = "12")]
+    pub cancelled_by: ::prost::alloc::string::String,
+    /// id of the waitlist entry the reservation is promoted from, 0 if reserved
+    /// directly
+    #[prost(int64, tag = "13")]
+    pub waitlist_id: i64,
+}
+/// Recurring reservation, expanded to reservations by the recurrence rule
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationSeries {
+    /// unique id for the series, should be empty when reserving
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// user id for the series
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    /// resource id for the series
+    #[prost(string, tag = "3")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// RFC 5545 recurrence rule, e.g. "FREQ=WEEKLY;BYDAY=MO,WE;COUNT=10".
+    /// FREQ could be DAILY, WEEKLY or MONTHLY, and either COUNT or UNTIL is
+    /// required
+    #[prost(string, tag = "4")]
+    pub rrule: ::prost::alloc::string::String,
+    /// IANA timezone the rule is evaluated in, e.g. "America/Los_Angeles"
+    #[prost(string, tag = "5")]
+    pub timezone: ::prost::alloc::string::String,
+    /// start time of the first occurrence
+    #[prost(message, optional, tag = "6")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the first occurrence
+    #[prost(message, optional, tag = "7")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// extra note
+    #[prost(string, tag = "8")]
+    pub note: ::prost::alloc::string::String,
+    /// start times of the occurrences excluded from the series
+    #[prost(message, repeated, tag = "9")]
+    pub exdates: ::prost::alloc::vec::Vec<::prost_types::Timestamp>,
+}
+/// Resource which could be reserved. Reservations could only be made on known
+/// resources which are not retired
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Resource {
+    /// unique id for the resource, e.g. "ocean-view-room-417"
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// display name of the resource
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// type of the resource, e.g. "room", "desk"
+    #[prost(string, tag = "3")]
+    pub resource_type: ::prost::alloc::string::String,
+    /// tags to group resources, e.g. "projector", "sea-view"
+    #[prost(string, repeated, tag = "4")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// IANA timezone the resource is located in, "UTC" if empty
+    #[prost(string, tag = "5")]
+    pub timezone: ::prost::alloc::string::String,
+    /// number of reservations the resource could hold at the same time, 1 if 0
+    #[prost(int32, tag = "6")]
+    pub capacity: i32,
+    /// retired resource is kept for its reservations, but can't be reserved
+    #[prost(bool, tag = "7")]
+    pub retired: bool,
+    /// turnover time needed before each reservation, e.g. setup
+    #[prost(message, optional, tag = "8")]
+    pub buffer_before: ::core::option::Option<::prost_types::Duration>,
+    /// turnover time needed after each reservation, e.g. cleanup or calibration
+    #[prost(message, optional, tag = "9")]
+    pub buffer_after: ::core::option::Option<::prost_types::Duration>,
 }
 /// To make a reservation, send a ReserveRequest with Reservation object (id
 /// should be empty)
@@ -31,20 +115,130 @@ pub struct Reservation {
 pub struct ReserveRequest {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// if the reservation conflicts, suggest up to this number of free windows in
+    /// the ConflictDetails of the error, no more than 100
+    #[prost(int32, tag = "2")]
+    pub suggestions: i32,
+    /// if the reservation conflicts, join the waitlist of the window instead of
+    /// failing
+    #[prost(bool, tag = "3")]
+    pub waitlist: bool,
 }
-/// Created reservation will be returned in ReserveRequest
+/// Created reservation will be returned in ReserveRequest, or the waitlist entry
+/// if it conflicts and the waitlist is asked
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReserveResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
+    #[prost(message, optional, tag = "2")]
+    pub waitlisted: ::core::option::Option<WaitlistEntry>,
+}
+/// To make several reservations at once, send a ReserveBatchRequest. Either
+/// all of them are made or none of them
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// Created reservations will be returned in ReserveBatchResponse, in the same
+/// order as requested
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To make a recurring reservation, send a ReserveSeriesRequest (id should be
+/// empty). Either all the occurrences are reserved or none of them
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSeriesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+}
+/// Created series and its occurrences will be returned in
+/// ReserveSeriesResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSeriesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To cancel a single occurrence of a series, send a CancelOccurrenceRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelOccurrenceRequest {
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+    /// original start time of the occurrence (recurrence_id of the reservation)
+    #[prost(message, optional, tag = "2")]
+    pub recurrence_id: ::core::option::Option<::prost_types::Timestamp>,
+    /// why the occurrence is cancelled
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+    /// who cancels the occurrence, e.g. the user id
+    #[prost(string, tag = "4")]
+    pub cancelled_by: ::prost::alloc::string::String,
+}
+/// Canceled occurrence will be returned in CancelOccurrenceResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelOccurrenceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To change an occurrence and all the following ones, send an
+/// UpdateSeriesRequest. The occurrences from recurrence_id on are replaced by
+/// a new series, the ones before stay in the original series
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesRequest {
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+    /// original start time of the first occurrence to change
+    #[prost(message, optional, tag = "2")]
+    pub recurrence_id: ::core::option::Option<::prost_types::Timestamp>,
+    /// new series for the following occurrences
+    #[prost(message, optional, tag = "3")]
+    pub series: ::core::option::Option<ReservationSeries>,
+}
+/// New series and its occurrences will be returned in UpdateSeriesResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To cancel a whole series, send a CancelSeriesRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesRequest {
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+    /// why the occurrences are cancelled
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+    /// who cancels the occurrences, e.g. the user id
+    #[prost(string, tag = "3")]
+    pub cancelled_by: ::prost::alloc::string::String,
+}
+/// Canceled occurrences will be returned in CancelSeriesResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
 }
-/// To make a reservation, send an UpdateRequest. Only note is updateable
+/// To update a reservation, send an UpdateRequest. The fields listed in
+/// update_mask (note, resource_id, user_id, start, end) are copied from
+/// reservation. If update_mask is empty, only note is updated
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateRequest {
     #[prost(int64, tag = "1")]
     pub id: i64,
     #[prost(string, tag = "2")]
     pub note: ::prost::alloc::string::String,
+    /// partial reservation carrying the new values
+    #[prost(message, optional, tag = "3")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// fields to update
+    #[prost(message, optional, tag = "4")]
+    pub update_mask: ::core::option::Option<::prost_types::FieldMask>,
 }
 /// Update reservation will be returned in UpdateREsponse
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -52,6 +246,24 @@ pub struct UpdateResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To move a reservation to another time window, send a RescheduleRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// new start time of the reservation
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// new end time of the reservation
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Rescheduled reservation will be returned in RescheduleResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
 /// To Change a reservation from pending to confirmed, send a ConfirmRequest
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConfirmRequest {
@@ -64,11 +276,18 @@ pub struct ConfirmResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
-/// To cancel a reservation, send a CancelRequest
+/// To cancel a reservation, send a CancelRequest. The reservation is kept with
+/// the cancelled status, so it could be restored later
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CancelRequest {
     #[prost(int64, tag = "1")]
     pub id: i64,
+    /// why the reservation is cancelled
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+    /// who cancels the reservation, e.g. the user id
+    #[prost(string, tag = "3")]
+    pub cancelled_by: ::prost::alloc::string::String,
 }
 /// Canceled reservation will be returned in CancelResponse
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -76,6 +295,145 @@ pub struct CancelResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To re-activate a cancelled reservation, send a RestoreRequest. It's restored
+/// with the status before it's cancelled if its window is still free
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+/// Restored reservation will be returned in RestoreResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To block a resource for maintenance, send a BlockRequest. The block is a
+/// reservation with the BLOCKED status taking all the seats of the resource
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// the window to block, the first one if recurring
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// why the resource is blocked, kept as the note of the block
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
+    /// who blocks the resource, kept as the user id of the block
+    #[prost(string, tag = "5")]
+    pub blocked_by: ::prost::alloc::string::String,
+    /// RFC 5545 recurrence rule to repeat the block, empty for a single block
+    #[prost(string, tag = "6")]
+    pub rrule: ::prost::alloc::string::String,
+    /// IANA timezone the recurrence rule is evaluated in, "UTC" if empty
+    #[prost(string, tag = "7")]
+    pub timezone: ::prost::alloc::string::String,
+    /// cancel the overlapping user reservations instead of failing, they are
+    /// notified by the changes as other cancellations
+    #[prost(bool, tag = "8")]
+    pub cancel_conflicts: bool,
+}
+/// Created blocks will be returned in BlockResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockResponse {
+    /// one block for each occurrence if recurring
+    #[prost(message, repeated, tag = "1")]
+    pub blocks: ::prost::alloc::vec::Vec<Reservation>,
+    /// id of the series if recurring, lift all of them by cancel_series
+    #[prost(int64, tag = "2")]
+    pub series_id: i64,
+    /// user reservations cancelled for the blocks
+    #[prost(message, repeated, tag = "3")]
+    pub cancelled: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To list the blocks, send a ListBlocksRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBlocksRequest {
+    /// if empty, list the blocks of all resources
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// list the blocks overlapping the window, unbounded if empty
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Blocks will be returned in ListBlocksResponse, order by start time
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBlocksResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub blocks: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To lift a block, send a LiftBlockRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiftBlockRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+/// Lifted block will be returned in LiftBlockResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiftBlockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub block: ::core::option::Option<Reservation>,
+}
+/// A request waiting for its window on the resource. It's promoted to a pending
+/// reservation once the window is free, first come first served
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitlistEntry {
+    /// unique id for the entry
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// user id for the reservation
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    /// resource id for the reservation
+    #[prost(string, tag = "3")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// start time for the reservation
+    #[prost(message, optional, tag = "4")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time for the reservation
+    #[prost(message, optional, tag = "5")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// extra note
+    #[prost(string, tag = "6")]
+    pub note: ::prost::alloc::string::String,
+    /// reservation the entry is promoted to, 0 if still waiting
+    #[prost(int64, tag = "7")]
+    pub reservation_id: i64,
+    /// when the entry joined the waitlist
+    #[prost(message, optional, tag = "8")]
+    pub joined_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// To list the entries still waiting, send a ListWaitlistRequest. Empty
+/// resource_id for all the resources
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWaitlistRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+/// Waiting entries will be returned in ListWaitlistResponse, in the order they
+/// joined
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWaitlistResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<WaitlistEntry>,
+}
+/// To leave the waitlist, send a LeaveWaitlistRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+/// Removed entry will be returned in LeaveWaitlistResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistResponse {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
 /// To get a reservation, send a GetRequest
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetRequest {
@@ -117,6 +475,11 @@ pub struct ReservationQuery {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// answer the query as the reservations were at this moment, rebuilt from
+    /// their history. Ignored by live_query
+    #[prost(message, optional, tag = "7")]
+    #[builder(setter(into, strip_option), default)]
+    pub as_of: ::core::option::Option<::prost_types::Timestamp>,
 }
 /// To query a reservation, send a QueryRequest
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -152,6 +515,11 @@ pub struct ReservationFilter {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// filter the reservations as they were at this moment, rebuilt from their
+    /// history
+    #[prost(message, optional, tag = "7")]
+    #[builder(setter(into, strip_option), default)]
+    pub as_of: ::core::option::Option<::prost_types::Timestamp>,
 }
 /// To query a reservation, send a QueryRequest
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -178,107 +546,430 @@ pub struct FilterResponse {
 }
 /// Client can listen to reservation updates by sending a ListenRequest
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListenRequest {}
+pub struct ListenRequest {
+    /// last seen change id. If set, all the changes after it will be replayed
+    /// before the live ones, otherwise only the live changes will be sent
+    #[prost(int64, optional, tag = "1")]
+    pub offset: ::core::option::Option<i64>,
+    /// consumer group name. If set, the changes will be sent from the group's
+    /// acknowledged offset (offset will be ignored), and the changes not
+    /// acknowledged in time will be redelivered
+    #[prost(string, tag = "2")]
+    pub group: ::prost::alloc::string::String,
+    /// only send the changes of this resource. If empty, send all resources
+    #[prost(string, tag = "3")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// only send the changes of this user. If empty, send all users
+    #[prost(string, tag = "4")]
+    pub user_id: ::prost::alloc::string::String,
+    /// glob pattern of resource ids, `*` matches any characters and `?` matches
+    /// one character, e.g. "ocean-view-*". If empty, send all resources
+    #[prost(string, tag = "5")]
+    pub resource_pattern: ::prost::alloc::string::String,
+    /// only send these update types. If empty, send all update types
+    #[prost(enumeration = "ReservationUpdateType", repeated, tag = "6")]
+    pub ops: ::prost::alloc::vec::Vec<i32>,
+}
 /// Server will send ListenResponse to client
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListenResponse {
     /// udpate type
-    #[prost(enumeration = "ReservationStatus", tag = "1")]
+    #[prost(enumeration = "ReservationUpdateType", tag = "1")]
     pub op: i32,
-    /// id for udpate reservation
-    #[prost(message, optional, tag = "2")]
+    /// id for the change, could be used as offset to resume listening
+    #[prost(int64, tag = "3")]
+    pub id: i64,
+    /// reservation before the change, empty if op is CREATE
+    #[prost(message, optional, tag = "4")]
+    pub old: ::core::option::Option<Reservation>,
+    /// reservation after the change, empty if op is DELETE
+    #[prost(message, optional, tag = "5")]
+    pub new: ::core::option::Option<Reservation>,
+    /// when the change happened
+    #[prost(message, optional, tag = "6")]
+    pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// To get the audit history of a reservation, send a HistoryRequest. Deleted
+/// reservations keep their history
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+/// An entry in the audit history of a reservation
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryEntry {
+    /// id of the entry, increasing in the order of the changes
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// update type
+    #[prost(enumeration = "ReservationUpdateType", tag = "2")]
+    pub op: i32,
+    /// who made the change, empty if unknown
+    #[prost(string, tag = "3")]
+    pub actor: ::prost::alloc::string::String,
+    /// when the change happened
+    #[prost(message, optional, tag = "4")]
+    pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// reservation after the change, or before it if op is DELETE
+    #[prost(message, optional, tag = "5")]
     pub reservation: ::core::option::Option<Reservation>,
 }
-/// reservation status for a given time period
-#[derive(sqlx::Type)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReservationStatus {
-    Unknown = 0,
-    Pending = 1,
-    Confirmed = 2,
-    Blocked = 3,
+/// History entries will be returned in HistoryResponse, the oldest first
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<HistoryEntry>,
 }
-impl ReservationStatus {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
-            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
-            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
-            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
-        }
-    }
+/// To acknowledge the changes a consumer group has processed, send an AckRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckRequest {
+    /// consumer group name
+    #[prost(string, tag = "1")]
+    pub group: ::prost::alloc::string::String,
+    /// id of the last processed change, all the changes before it are
+    /// acknowledged as well
+    #[prost(int64, tag = "2")]
+    pub id: i64,
 }
-/// when reservation is updated, record the update type
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReservationUpdateType {
-    Unknown = 0,
-    Create = 1,
-    Update = 2,
-    Delete = 3,
+/// The acknowledged offset of the consumer group will be returned in
+/// AckResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckResponse {
+    #[prost(int64, tag = "1")]
+    pub offset: i64,
 }
-impl ReservationUpdateType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
-            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
-            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
-            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
-        }
-    }
+/// To add a resource, send a CreateResourceRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
 }
-/// Generated client implementations.
-pub mod reservation_service_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    /// Reservation service
-    #[derive(Debug, Clone)]
-    pub struct ReservationServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl ReservationServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: std::convert::TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> ReservationServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> ReservationServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
+/// Created resource will be returned in CreateResourceResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To get a resource, send a GetResourceRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Resource will be returned in GetResourceResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To update a resource, send an UpdateResourceRequest. The fields listed in
+/// update_mask (name, resource_type, tags, timezone, capacity, retired,
+/// buffer_before, buffer_after) are copied from resource. Lowering the capacity
+/// or raising the buffers fails if the reservations not ended yet wouldn't fit
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub resource: ::core::option::Option<Resource>,
+    #[prost(message, optional, tag = "3")]
+    pub update_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+/// Updated resource will be returned in UpdateResourceResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To delete a resource, send a DeleteResourceRequest. Resources with
+/// reservations can't be deleted, retire them instead
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Deleted resource will be returned in DeleteResourceResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To list resources, send a ListResourcesRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesRequest {
+    /// include the retired resources
+    #[prost(bool, tag = "1")]
+    pub include_retired: bool,
+}
+/// Resources ordered by id will be returned in ListResourcesResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub resources: ::prost::alloc::vec::Vec<Resource>,
+}
+/// A window of time, both the start and the end are included
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimeSlot {
+    #[prost(message, optional, tag = "1")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "2")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// To find the free time of a resource, send an AvailabilityRequest
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// start of the search window
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end of the search window
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// minimal length of the free slots
+    #[prost(message, optional, tag = "4")]
+    pub duration: ::core::option::Option<::prost_types::Duration>,
+    /// if set, free slots are shrunk to start and end at start + n * granularity
+    #[prost(message, optional, tag = "5")]
+    pub granularity: ::core::option::Option<::prost_types::Duration>,
+}
+/// Free slots within the search window, ordered by start time, will be
+/// returned in AvailabilityResponse
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub slots: ::prost::alloc::vec::Vec<TimeSlot>,
+}
+/// Conditions to find the resources free for a time window
+#[derive(derive_builder::Builder)]
+#[builder(build_fn(name = "private_build"))]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceSearch {
+    /// resource should have all the tags
+    #[prost(string, repeated, tag = "1")]
+    #[builder(setter(into), default)]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// resource type, any type if empty
+    #[prost(string, tag = "2")]
+    #[builder(setter(into), default)]
+    pub resource_type: ::prost::alloc::string::String,
+    /// minimal capacity of the resource
+    #[prost(int32, tag = "3")]
+    #[builder(setter(into), default)]
+    pub min_capacity: i32,
+    /// start of the time window
+    #[prost(message, optional, tag = "4")]
+    #[builder(setter(into, strip_option), default)]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end of the time window
+    #[prost(message, optional, tag = "5")]
+    #[builder(setter(into, strip_option), default)]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// max number of candidates to return, 10 if 0
+    #[prost(int32, tag = "6")]
+    #[builder(setter(into), default)]
+    pub limit: i32,
+}
+/// Resource free for the time window
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceCandidate {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+    /// most reservations overlapping each other in the time window
+    #[prost(int32, tag = "2")]
+    pub taken: i32,
+}
+/// To find the resources free for a time window, send a FindAvailableRequest.
+/// If reserve is set, the best candidate is reserved for the user
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindAvailableRequest {
+    #[prost(message, optional, tag = "1")]
+    pub search: ::core::option::Option<ResourceSearch>,
+    #[prost(bool, tag = "2")]
+    pub reserve: bool,
+    /// user id of the reservation, required if reserve is set
+    #[prost(string, tag = "3")]
+    pub user_id: ::prost::alloc::string::String,
+    /// note of the reservation
+    #[prost(string, tag = "4")]
+    pub note: ::prost::alloc::string::String,
+}
+/// Candidates will be returned in FindAvailableResponse, the best fit first:
+/// the smallest capacity, then the least taken, then by resource id
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindAvailableResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub candidates: ::prost::alloc::vec::Vec<ResourceCandidate>,
+    /// the reservation made on the best candidate, if reserve is set
+    #[prost(message, optional, tag = "2")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// Free window with the same duration as a conflicting reservation
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Suggestion {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Details of the FAILED_PRECONDITION error when a reservation conflicts
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConflictDetails {
+    /// resource of the conflicting reservation
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// number of reservations the resource could hold at the same time
+    #[prost(int32, tag = "2")]
+    pub capacity: i32,
+    /// ids of the reservations taking all the seats
+    #[prost(int64, repeated, tag = "3")]
+    pub taken: ::prost::alloc::vec::Vec<i64>,
+    /// nearest free windows on the same resource first, then the requested
+    /// window on similar resources
+    #[prost(message, repeated, tag = "4")]
+    pub suggestions: ::prost::alloc::vec::Vec<Suggestion>,
+}
+/// Server will send LiveQueryEvent to client for live query
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiveQueryEvent {
+    /// event type
+    #[prost(enumeration = "LiveQueryEventType", tag = "1")]
+    pub r#type: i32,
+    /// the reservation, empty if type is SYNCED. For REMOVE, it is the
+    /// reservation before it left the result set
+    #[prost(message, optional, tag = "2")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// id of the last change applied to the result set
+    #[prost(int64, tag = "3")]
+    pub offset: i64,
+}
+/// reservation status for a given time period
+#[derive(sqlx::Type)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationStatus {
+    Unknown = 0,
+    Pending = 1,
+    Confirmed = 2,
+    Blocked = 3,
+    Cancelled = 4,
+}
+impl ReservationStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
+            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
+            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
+            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
+            ReservationStatus::Cancelled => "RESERVATION_STATUS_CANCELLED",
+        }
+    }
+}
+/// when reservation is updated, record the update type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationUpdateType {
+    Unknown = 0,
+    Create = 1,
+    Update = 2,
+    Delete = 3,
+}
+impl ReservationUpdateType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
+            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
+            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
+            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
+        }
+    }
+}
+/// type of the events sent by live query
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum LiveQueryEventType {
+    Unknown = 0,
+    /// reservation matching the query when the live query started
+    Snapshot = 1,
+    /// all the snapshot events have been sent, the following events are deltas
+    Synced = 2,
+    /// reservation entered the result set
+    Insert = 3,
+    /// reservation in the result set has been changed
+    Update = 4,
+    /// reservation left the result set
+    Remove = 5,
+}
+impl LiveQueryEventType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            LiveQueryEventType::Unknown => "LIVE_QUERY_EVENT_TYPE_UNKNOWN",
+            LiveQueryEventType::Snapshot => "LIVE_QUERY_EVENT_TYPE_SNAPSHOT",
+            LiveQueryEventType::Synced => "LIVE_QUERY_EVENT_TYPE_SYNCED",
+            LiveQueryEventType::Insert => "LIVE_QUERY_EVENT_TYPE_INSERT",
+            LiveQueryEventType::Update => "LIVE_QUERY_EVENT_TYPE_UPDATE",
+            LiveQueryEventType::Remove => "LIVE_QUERY_EVENT_TYPE_REMOVE",
+        }
+    }
+}
+/// Generated client implementations.
+pub mod reservation_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Reservation service
+    #[derive(Debug, Clone)]
+    pub struct ReservationServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ReservationServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ReservationServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ReservationServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
             T: tonic::codegen::Service<
                 http::Request<tonic::body::BoxBody>,
                 Response = http::Response<
@@ -326,11 +1017,12 @@ pub mod reservation_service_client {
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        pub async fn confirm(
+        /// make several reservations in one transaction, if any of them conflicts,
+        /// none is made and all the conflicts are reported
+        pub async fn reserve_batch(
             &mut self,
-            request: impl tonic::IntoRequest<super::ConfirmRequest>,
-        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveBatchRequest>,
+        ) -> Result<tonic::Response<super::ReserveBatchResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -342,15 +1034,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/confirm",
+                "/reservation.ReservationService/reserve_batch",
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// update the reservation note
-        pub async fn update(
+        /// make a recurring reservation
+        pub async fn reserve_series(
             &mut self,
-            request: impl tonic::IntoRequest<super::UpdateRequest>,
-        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveSeriesRequest>,
+        ) -> Result<tonic::Response<super::ReserveSeriesResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -362,15 +1054,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/update",
+                "/reservation.ReservationService/reserve_series",
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// cancel a reservation
-        pub async fn cancel(
+        /// cancel a single occurrence of a series
+        pub async fn cancel_occurrence(
             &mut self,
-            request: impl tonic::IntoRequest<super::CancelRequest>,
-        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::CancelOccurrenceRequest>,
+        ) -> Result<tonic::Response<super::CancelOccurrenceResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -382,15 +1074,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/cancel",
+                "/reservation.ReservationService/cancel_occurrence",
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// get a reservation by id
-        pub async fn get(
+        /// change an occurrence of a series and all the following ones
+        pub async fn update_series(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetRequest>,
-        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -402,18 +1094,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/get",
+                "/reservation.ReservationService/update_series",
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// query reservations by resource id, user id, status, start time, end time
-        pub async fn query(
+        /// cancel all the occurrences of a series
+        pub async fn cancel_series(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryRequest>,
-        ) -> Result<
-            tonic::Response<tonic::codec::Streaming<super::Reservation>>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -425,15 +1114,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/query",
+                "/reservation.ReservationService/cancel_series",
             );
-            self.inner.server_streaming(request.into_request(), path, codec).await
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        /// filter reservations, order by reservation id
-        pub async fn filter(
+        /// confirm a pending reservation, if reservation is not pending, do nothing
+        pub async fn confirm(
             &mut self,
-            request: impl tonic::IntoRequest<super::FilterRequest>,
-        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ConfirmRequest>,
+        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -445,18 +1134,15 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/filter",
+                "/reservation.ReservationService/confirm",
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// another system could monitor newly added/confirmed/canceled reservations
-        pub async fn listen(
+        /// update the reservation fields listed in the field mask
+        pub async fn update(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListenRequest>,
-        ) -> Result<
-            tonic::Response<tonic::codec::Streaming<super::Reservation>>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::UpdateRequest>,
+        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -468,150 +1154,1385 @@ pub mod reservation_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/reservation.ReservationService/listen",
+                "/reservation.ReservationService/update",
             );
-            self.inner.server_streaming(request.into_request(), path, codec).await
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// move a reservation to another time window, fail if it conflicts with
+        /// other reservations
+        pub async fn reschedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reschedule",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod reservation_service_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
-    #[async_trait]
-    pub trait ReservationService: Send + Sync + 'static {
-        /// make a reservation
-        async fn reserve(
-            &self,
-            request: tonic::Request<super::ReserveRequest>,
-        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        async fn confirm(
-            &self,
-            request: tonic::Request<super::ConfirmRequest>,
-        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
-        /// update the reservation note
-        async fn update(
-            &self,
-            request: tonic::Request<super::UpdateRequest>,
-        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
         /// cancel a reservation
-        async fn cancel(
-            &self,
-            request: tonic::Request<super::CancelRequest>,
-        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status>;
-        /// get a reservation by id
-        async fn get(
-            &self,
-            request: tonic::Request<super::GetRequest>,
-        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
-        /// Server streaming response type for the query method.
-        type queryStream: futures_core::Stream<
-                Item = Result<super::Reservation, tonic::Status>,
-            >
-            + Send
-            + 'static;
-        /// query reservations by resource id, user id, status, start time, end time
-        async fn query(
-            &self,
-            request: tonic::Request<super::QueryRequest>,
-        ) -> Result<tonic::Response<Self::queryStream>, tonic::Status>;
-        /// filter reservations, order by reservation id
-        async fn filter(
-            &self,
-            request: tonic::Request<super::FilterRequest>,
-        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status>;
-        /// Server streaming response type for the listen method.
-        type listenStream: futures_core::Stream<
-                Item = Result<super::Reservation, tonic::Status>,
-            >
-            + Send
-            + 'static;
-        /// another system could monitor newly added/confirmed/canceled reservations
-        async fn listen(
-            &self,
-            request: tonic::Request<super::ListenRequest>,
-        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
-    }
-    /// Reservation service
-    #[derive(Debug)]
-    pub struct ReservationServiceServer<T: ReservationService> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: ReservationService> ReservationServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        pub async fn cancel(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelRequest>,
+        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-            }
+        /// restore a cancelled reservation if its window is still free
+        pub async fn restore(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RestoreRequest>,
+        ) -> Result<tonic::Response<super::RestoreResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/restore",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        /// block a resource for maintenance
+        pub async fn block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BlockRequest>,
+        ) -> Result<tonic::Response<super::BlockResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/block",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        /// list the blocks of the resources
+        pub async fn list_blocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBlocksRequest>,
+        ) -> Result<tonic::Response<super::ListBlocksResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_blocks",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        /// lift a block, the resource could be reserved again
+        pub async fn lift_block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LiftBlockRequest>,
+        ) -> Result<tonic::Response<super::LiftBlockResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/lift_block",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
-    where
-        T: ReservationService,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        /// list the entries waiting for their windows
+        pub async fn list_waitlist(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::ListWaitlistRequest>,
+        ) -> Result<tonic::Response<super::ListWaitlistResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_waitlist",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            let inner = self.inner.clone();
-            match req.uri().path() {
-                "/reservation.ReservationService/reserve" => {
+        /// leave the waitlist before the entry is promoted
+        pub async fn leave_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LeaveWaitlistRequest>,
+        ) -> Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/leave_waitlist",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a reservation by id
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetRequest>,
+        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// query reservations by resource id, user id, status, start time, end time
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryRequest>,
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::Reservation>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/query",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
+        }
+        /// filter reservations, order by reservation id
+        pub async fn filter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FilterRequest>,
+        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/filter",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// another system could monitor newly added/confirmed/canceled reservations.
+        /// A listener falling too far behind gets RESOURCE_EXHAUSTED, and could
+        /// resume from the offset in the message
+        pub async fn listen(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListenRequest>,
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::ListenResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/listen",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
+        }
+        /// acknowledge the changes processed by a consumer group
+        pub async fn ack(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AckRequest>,
+        ) -> Result<tonic::Response<super::AckResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/ack",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// who changed the reservation and when, including deleted reservations
+        pub async fn history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/history",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// query reservations, then keep sending the deltas of the result set
+        pub async fn live_query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryRequest>,
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::LiveQueryEvent>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/live_query",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
+        }
+        /// add a resource which could be reserved
+        pub async fn create_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/create_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a resource by id
+        pub async fn get_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// update the resource fields listed in the field mask
+        pub async fn update_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// delete a resource without reservations
+        pub async fn delete_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteResourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/delete_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// list resources, order by resource id
+        pub async fn list_resources(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListResourcesRequest>,
+        ) -> Result<tonic::Response<super::ListResourcesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_resources",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// find the free slots of a resource, a slot is free if the resource still
+        /// has capacity for another reservation all the time
+        pub async fn availability(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/availability",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// find the resources matching the conditions and free for the time window,
+        /// optionally reserve the best candidate
+        pub async fn find_available(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindAvailableRequest>,
+        ) -> Result<tonic::Response<super::FindAvailableResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/find_available",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod reservation_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
+    #[async_trait]
+    pub trait ReservationService: Send + Sync + 'static {
+        /// make a reservation
+        async fn reserve(
+            &self,
+            request: tonic::Request<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
+        /// make several reservations in one transaction, if any of them conflicts,
+        /// none is made and all the conflicts are reported
+        async fn reserve_batch(
+            &self,
+            request: tonic::Request<super::ReserveBatchRequest>,
+        ) -> Result<tonic::Response<super::ReserveBatchResponse>, tonic::Status>;
+        /// make a recurring reservation
+        async fn reserve_series(
+            &self,
+            request: tonic::Request<super::ReserveSeriesRequest>,
+        ) -> Result<tonic::Response<super::ReserveSeriesResponse>, tonic::Status>;
+        /// cancel a single occurrence of a series
+        async fn cancel_occurrence(
+            &self,
+            request: tonic::Request<super::CancelOccurrenceRequest>,
+        ) -> Result<tonic::Response<super::CancelOccurrenceResponse>, tonic::Status>;
+        /// change an occurrence of a series and all the following ones
+        async fn update_series(
+            &self,
+            request: tonic::Request<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status>;
+        /// cancel all the occurrences of a series
+        async fn cancel_series(
+            &self,
+            request: tonic::Request<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status>;
+        /// confirm a pending reservation, if reservation is not pending, do nothing
+        async fn confirm(
+            &self,
+            request: tonic::Request<super::ConfirmRequest>,
+        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
+        /// update the reservation fields listed in the field mask
+        async fn update(
+            &self,
+            request: tonic::Request<super::UpdateRequest>,
+        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
+        /// move a reservation to another time window, fail if it conflicts with
+        /// other reservations
+        async fn reschedule(
+            &self,
+            request: tonic::Request<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status>;
+        /// cancel a reservation
+        async fn cancel(
+            &self,
+            request: tonic::Request<super::CancelRequest>,
+        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status>;
+        /// restore a cancelled reservation if its window is still free
+        async fn restore(
+            &self,
+            request: tonic::Request<super::RestoreRequest>,
+        ) -> Result<tonic::Response<super::RestoreResponse>, tonic::Status>;
+        /// block a resource for maintenance
+        async fn block(
+            &self,
+            request: tonic::Request<super::BlockRequest>,
+        ) -> Result<tonic::Response<super::BlockResponse>, tonic::Status>;
+        /// list the blocks of the resources
+        async fn list_blocks(
+            &self,
+            request: tonic::Request<super::ListBlocksRequest>,
+        ) -> Result<tonic::Response<super::ListBlocksResponse>, tonic::Status>;
+        /// lift a block, the resource could be reserved again
+        async fn lift_block(
+            &self,
+            request: tonic::Request<super::LiftBlockRequest>,
+        ) -> Result<tonic::Response<super::LiftBlockResponse>, tonic::Status>;
+        /// list the entries waiting for their windows
+        async fn list_waitlist(
+            &self,
+            request: tonic::Request<super::ListWaitlistRequest>,
+        ) -> Result<tonic::Response<super::ListWaitlistResponse>, tonic::Status>;
+        /// leave the waitlist before the entry is promoted
+        async fn leave_waitlist(
+            &self,
+            request: tonic::Request<super::LeaveWaitlistRequest>,
+        ) -> Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status>;
+        /// get a reservation by id
+        async fn get(
+            &self,
+            request: tonic::Request<super::GetRequest>,
+        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        /// Server streaming response type for the query method.
+        type queryStream: futures_core::Stream<
+                Item = Result<super::Reservation, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// query reservations by resource id, user id, status, start time, end time
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryRequest>,
+        ) -> Result<tonic::Response<Self::queryStream>, tonic::Status>;
+        /// filter reservations, order by reservation id
+        async fn filter(
+            &self,
+            request: tonic::Request<super::FilterRequest>,
+        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status>;
+        /// Server streaming response type for the listen method.
+        type listenStream: futures_core::Stream<
+                Item = Result<super::ListenResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// another system could monitor newly added/confirmed/canceled reservations.
+        /// A listener falling too far behind gets RESOURCE_EXHAUSTED, and could
+        /// resume from the offset in the message
+        async fn listen(
+            &self,
+            request: tonic::Request<super::ListenRequest>,
+        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
+        /// acknowledge the changes processed by a consumer group
+        async fn ack(
+            &self,
+            request: tonic::Request<super::AckRequest>,
+        ) -> Result<tonic::Response<super::AckResponse>, tonic::Status>;
+        /// who changed the reservation and when, including deleted reservations
+        async fn history(
+            &self,
+            request: tonic::Request<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status>;
+        /// Server streaming response type for the live_query method.
+        type live_queryStream: futures_core::Stream<
+                Item = Result<super::LiveQueryEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// query reservations, then keep sending the deltas of the result set
+        async fn live_query(
+            &self,
+            request: tonic::Request<super::QueryRequest>,
+        ) -> Result<tonic::Response<Self::live_queryStream>, tonic::Status>;
+        /// add a resource which could be reserved
+        async fn create_resource(
+            &self,
+            request: tonic::Request<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status>;
+        /// get a resource by id
+        async fn get_resource(
+            &self,
+            request: tonic::Request<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status>;
+        /// update the resource fields listed in the field mask
+        async fn update_resource(
+            &self,
+            request: tonic::Request<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status>;
+        /// delete a resource without reservations
+        async fn delete_resource(
+            &self,
+            request: tonic::Request<super::DeleteResourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteResourceResponse>, tonic::Status>;
+        /// list resources, order by resource id
+        async fn list_resources(
+            &self,
+            request: tonic::Request<super::ListResourcesRequest>,
+        ) -> Result<tonic::Response<super::ListResourcesResponse>, tonic::Status>;
+        /// find the free slots of a resource, a slot is free if the resource still
+        /// has capacity for another reservation all the time
+        async fn availability(
+            &self,
+            request: tonic::Request<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status>;
+        /// find the resources matching the conditions and free for the time window,
+        /// optionally reserve the best candidate
+        async fn find_available(
+            &self,
+            request: tonic::Request<super::FindAvailableRequest>,
+        ) -> Result<tonic::Response<super::FindAvailableResponse>, tonic::Status>;
+    }
+    /// Reservation service
+    #[derive(Debug)]
+    pub struct ReservationServiceServer<T: ReservationService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ReservationService> ReservationServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
+    where
+        T: ReservationService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/reservation.ReservationService/reserve" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveRequest>
+                    for reserveSvc<T> {
+                        type Response = super::ReserveResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_batch" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_batchSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveBatchRequest>
+                    for reserve_batchSvc<T> {
+                        type Response = super::ReserveBatchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveBatchRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).reserve_batch(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_batchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveSeriesRequest>
+                    for reserve_seriesSvc<T> {
+                        type Response = super::ReserveSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).reserve_series(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_occurrence" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_occurrenceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelOccurrenceRequest>
+                    for cancel_occurrenceSvc<T> {
+                        type Response = super::CancelOccurrenceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelOccurrenceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).cancel_occurrence(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_occurrenceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct update_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateSeriesRequest>
+                    for update_seriesSvc<T> {
+                        type Response = super::UpdateSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_series(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = update_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelSeriesRequest>
+                    for cancel_seriesSvc<T> {
+                        type Response = super::CancelSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).cancel_series(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ConfirmRequest>
+                    for confirmSvc<T> {
+                        type Response = super::ConfirmResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).confirm(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirmSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateRequest>
+                    for updateSvc<T> {
+                        type Response = super::UpdateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = updateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reschedule" => {
+                    #[allow(non_camel_case_types)]
+                    struct rescheduleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::RescheduleRequest>
+                    for rescheduleSvc<T> {
+                        type Response = super::RescheduleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RescheduleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reschedule(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = rescheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelRequest>
+                    for cancelSvc<T> {
+                        type Response = super::CancelResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/restore" => {
+                    #[allow(non_camel_case_types)]
+                    struct restoreSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::RestoreRequest>
+                    for restoreSvc<T> {
+                        type Response = super::RestoreResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RestoreRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).restore(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = restoreSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/block" => {
+                    #[allow(non_camel_case_types)]
+                    struct blockSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::BlockRequest> for blockSvc<T> {
+                        type Response = super::BlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BlockRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).block(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = blockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_blocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_blocksSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ListBlocksRequest>
+                    for list_blocksSvc<T> {
+                        type Response = super::ListBlocksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBlocksRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_blocks(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_blocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/lift_block" => {
+                    #[allow(non_camel_case_types)]
+                    struct lift_blockSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::LiftBlockRequest>
+                    for lift_blockSvc<T> {
+                        type Response = super::LiftBlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LiftBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).lift_block(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = lift_blockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ListWaitlistRequest>
+                    for list_waitlistSvc<T> {
+                        type Response = super::ListWaitlistResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_waitlist(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/leave_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct leave_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::LeaveWaitlistRequest>
+                    for leave_waitlistSvc<T> {
+                        type Response = super::LeaveWaitlistResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LeaveWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).leave_waitlist(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = leave_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get" => {
+                    #[allow(non_camel_case_types)]
+                    struct getSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = getSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/query" => {
                     #[allow(non_camel_case_types)]
-                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
+                    struct querySvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::ReserveRequest>
-                    for reserveSvc<T> {
-                        type Response = super::ReserveResponse;
+                    > tonic::server::ServerStreamingService<super::QueryRequest>
+                    for querySvc<T> {
+                        type Response = super::Reservation;
+                        type ResponseStream = T::queryStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ReserveRequest>,
+                            request: tonic::Request<super::QueryRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).reserve(request).await };
+                            let fut = async move { (*inner).query(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -620,36 +2541,36 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = reserveSvc(inner);
+                        let method = querySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
                                 accept_compression_encodings,
                                 send_compression_encodings,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/confirm" => {
+                "/reservation.ReservationService/filter" => {
                     #[allow(non_camel_case_types)]
-                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
+                    struct filterSvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::ConfirmRequest>
-                    for confirmSvc<T> {
-                        type Response = super::ConfirmResponse;
+                    > tonic::server::UnaryService<super::FilterRequest>
+                    for filterSvc<T> {
+                        type Response = super::FilterResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ConfirmRequest>,
+                            request: tonic::Request<super::FilterRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).confirm(request).await };
+                            let fut = async move { (*inner).filter(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -658,7 +2579,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = confirmSvc(inner);
+                        let method = filterSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -670,24 +2591,25 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/update" => {
+                "/reservation.ReservationService/listen" => {
                     #[allow(non_camel_case_types)]
-                    struct updateSvc<T: ReservationService>(pub Arc<T>);
+                    struct listenSvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::UpdateRequest>
-                    for updateSvc<T> {
-                        type Response = super::UpdateResponse;
+                    > tonic::server::ServerStreamingService<super::ListenRequest>
+                    for listenSvc<T> {
+                        type Response = super::ListenResponse;
+                        type ResponseStream = T::listenStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateRequest>,
+                            request: tonic::Request<super::ListenRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update(request).await };
+                            let fut = async move { (*inner).listen(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -696,36 +2618,35 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = updateSvc(inner);
+                        let method = listenSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
                                 accept_compression_encodings,
                                 send_compression_encodings,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/cancel" => {
+                "/reservation.ReservationService/ack" => {
                     #[allow(non_camel_case_types)]
-                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
+                    struct ackSvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::CancelRequest>
-                    for cancelSvc<T> {
-                        type Response = super::CancelResponse;
+                    > tonic::server::UnaryService<super::AckRequest> for ackSvc<T> {
+                        type Response = super::AckResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelRequest>,
+                            request: tonic::Request<super::AckRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).cancel(request).await };
+                            let fut = async move { (*inner).ack(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -734,7 +2655,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = cancelSvc(inner);
+                        let method = ackSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -746,23 +2667,24 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/get" => {
+                "/reservation.ReservationService/history" => {
                     #[allow(non_camel_case_types)]
-                    struct getSvc<T: ReservationService>(pub Arc<T>);
+                    struct historySvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
-                        type Response = super::GetResponse;
+                    > tonic::server::UnaryService<super::HistoryRequest>
+                    for historySvc<T> {
+                        type Response = super::HistoryResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetRequest>,
+                            request: tonic::Request<super::HistoryRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get(request).await };
+                            let fut = async move { (*inner).history(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -771,7 +2693,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = getSvc(inner);
+                        let method = historySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -783,15 +2705,15 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/query" => {
+                "/reservation.ReservationService/live_query" => {
                     #[allow(non_camel_case_types)]
-                    struct querySvc<T: ReservationService>(pub Arc<T>);
+                    struct live_querySvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
                     > tonic::server::ServerStreamingService<super::QueryRequest>
-                    for querySvc<T> {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::queryStream;
+                    for live_querySvc<T> {
+                        type Response = super::LiveQueryEvent;
+                        type ResponseStream = T::live_queryStream;
                         type Future = BoxFuture<
                             tonic::Response<Self::ResponseStream>,
                             tonic::Status,
@@ -801,7 +2723,7 @@ pub mod reservation_service_server {
                             request: tonic::Request<super::QueryRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).query(request).await };
+                            let fut = async move { (*inner).live_query(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -810,7 +2732,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = querySvc(inner);
+                        let method = live_querySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -822,24 +2744,26 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/filter" => {
+                "/reservation.ReservationService/create_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct filterSvc<T: ReservationService>(pub Arc<T>);
+                    struct create_resourceSvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::UnaryService<super::FilterRequest>
-                    for filterSvc<T> {
-                        type Response = super::FilterResponse;
+                    > tonic::server::UnaryService<super::CreateResourceRequest>
+                    for create_resourceSvc<T> {
+                        type Response = super::CreateResourceResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FilterRequest>,
+                            request: tonic::Request<super::CreateResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).filter(request).await };
+                            let fut = async move {
+                                (*inner).create_resource(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -848,7 +2772,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = filterSvc(inner);
+                        let method = create_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -860,25 +2784,26 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/listen" => {
+                "/reservation.ReservationService/get_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct listenSvc<T: ReservationService>(pub Arc<T>);
+                    struct get_resourceSvc<T: ReservationService>(pub Arc<T>);
                     impl<
                         T: ReservationService,
-                    > tonic::server::ServerStreamingService<super::ListenRequest>
-                    for listenSvc<T> {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::listenStream;
+                    > tonic::server::UnaryService<super::GetResourceRequest>
+                    for get_resourceSvc<T> {
+                        type Response = super::GetResourceResponse;
                         type Future = BoxFuture<
-                            tonic::Response<Self::ResponseStream>,
+                            tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListenRequest>,
+                            request: tonic::Request<super::GetResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).listen(request).await };
+                            let fut = async move {
+                                (*inner).get_resource(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -887,14 +2812,214 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = listenSvc(inner);
+                        let method = get_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
                                 accept_compression_encodings,
                                 send_compression_encodings,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update_resource" => {
+                    #[allow(non_camel_case_types)]
+                    struct update_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateResourceRequest>
+                    for update_resourceSvc<T> {
+                        type Response = super::UpdateResourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateResourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_resource(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = update_resourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/delete_resource" => {
+                    #[allow(non_camel_case_types)]
+                    struct delete_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::DeleteResourceRequest>
+                    for delete_resourceSvc<T> {
+                        type Response = super::DeleteResourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteResourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_resource(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = delete_resourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_resources" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_resourcesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ListResourcesRequest>
+                    for list_resourcesSvc<T> {
+                        type Response = super::ListResourcesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListResourcesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_resources(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_resourcesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/availability" => {
+                    #[allow(non_camel_case_types)]
+                    struct availabilitySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::AvailabilityRequest>
+                    for availabilitySvc<T> {
+                        type Response = super::AvailabilityResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AvailabilityRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).availability(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = availabilitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/find_available" => {
+                    #[allow(non_camel_case_types)]
+                    struct find_availableSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::FindAvailableRequest>
+                    for find_availableSvc<T> {
+                        type Response = super::FindAvailableResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindAvailableRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).find_available(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = find_availableSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
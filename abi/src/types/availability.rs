@@ -0,0 +1,218 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::postgres::types::PgRange;
+
+use crate::{
+    convert_to_duration, convert_to_pb_duration, convert_to_timestamp, convert_to_utc_time,
+    AvailabilityRequest, Error, TimeSlot, Validator,
+};
+
+use super::{get_timespan, validate_range};
+
+impl AvailabilityRequest {
+    pub fn new(
+        rid: impl Into<String>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        duration: Duration,
+        granularity: Option<Duration>,
+    ) -> Self {
+        Self {
+            resource_id: rid.into(),
+            start: Some(convert_to_timestamp(&start)),
+            end: Some(convert_to_timestamp(&end)),
+            duration: Some(convert_to_pb_duration(&duration)),
+            granularity: granularity.as_ref().map(convert_to_pb_duration),
+        }
+    }
+
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+
+    /// free slots in the search window, given the windows of the reservations on the resource.
+    /// A slot is free while fewer than capacity reservations overlap it. Reservations include
+    /// both their start and end, so a slot keeps a microsecond away from the ones around it
+    pub fn free_slots(
+        &self,
+        reserved: &[(DateTime<Utc>, DateTime<Utc>)],
+        capacity: usize,
+    ) -> Vec<TimeSlot> {
+        let start = convert_to_utc_time(self.start.as_ref().unwrap());
+        let end = convert_to_utc_time(self.end.as_ref().unwrap());
+        let duration = self.duration.as_ref().map(convert_to_duration).unwrap();
+        let granularity = self.granularity.as_ref().map(convert_to_duration);
+
+        // a reservation takes a seat from its start through its end, both included. Starts are
+        // sorted before ends at the same time, so back to back reservations overlap
+        let mut events: Vec<(DateTime<Utc>, bool)> = reserved
+            .iter()
+            .filter(|(s, e)| *s <= end && *e >= start)
+            .flat_map(|(s, e)| [(*s.max(&start), false), (*e.min(&end), true)])
+            .collect();
+        events.sort();
+
+        // a gap ends right before the seats are all taken, and starts right after one is freed
+        let mut gaps = Vec::new();
+        let mut taken = 0;
+        let mut free_since = Some(start);
+        for (at, freed) in events {
+            taken += if freed { -1 } else { 1 };
+            match free_since {
+                Some(since) if taken as usize >= capacity => {
+                    gaps.push((since, at - Duration::microseconds(1)));
+                    free_since = None;
+                }
+                None if (taken as usize) < capacity => {
+                    free_since = Some(at + Duration::microseconds(1))
+                }
+                _ => {}
+            }
+        }
+        if let Some(since) = free_since {
+            gaps.push((since, end));
+        }
+
+        gaps.into_iter()
+            .map(|(from, to)| match granularity {
+                Some(g) => (snap(start, from, g, true), snap(start, to, g, false)),
+                None => (from, to),
+            })
+            .filter(|(from, to)| *to - *from >= duration)
+            .map(|(from, to)| TimeSlot {
+                start: Some(convert_to_timestamp(&from)),
+                end: Some(convert_to_timestamp(&to)),
+            })
+            .collect()
+    }
+}
+
+impl Validator for AvailabilityRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+        match self.duration.as_ref().map(convert_to_duration) {
+            Some(d) if d > Duration::zero() => {}
+            _ => return Err(Error::InvalidDuration("duration".into())),
+        }
+        // the slots are aligned by whole seconds
+        match self.granularity.as_ref() {
+            Some(g) if g.seconds <= 0 || g.nanos != 0 => {
+                Err(Error::InvalidDuration("granularity".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// move the time to start + n * granularity, up or down
+fn snap(start: DateTime<Utc>, at: DateTime<Utc>, granularity: Duration, up: bool) -> DateTime<Utc> {
+    let g = granularity.num_seconds();
+    let offset = (at - start).num_seconds();
+    let mut n = offset / g;
+    if up && start + Duration::seconds(n * g) < at {
+        n += 1;
+    }
+    start + Duration::seconds(n * g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(s: &str) -> DateTime<Utc> {
+        format!("2023-01-25T{s}:00-0700").parse().unwrap()
+    }
+
+    /// right after a reservation ends
+    fn after(s: &str) -> DateTime<Utc> {
+        t(s) + Duration::microseconds(1)
+    }
+
+    /// right before a reservation starts
+    fn before(s: &str) -> DateTime<Utc> {
+        t(s) - Duration::microseconds(1)
+    }
+
+    fn slots(slots: Vec<TimeSlot>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        slots
+            .iter()
+            .map(|s| {
+                (
+                    convert_to_utc_time(s.start.as_ref().unwrap()),
+                    convert_to_utc_time(s.end.as_ref().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    fn make_request(duration: i64, granularity: Option<i64>) -> AvailabilityRequest {
+        AvailabilityRequest::new(
+            "ocean-view-room-417",
+            t("09:00"),
+            t("18:00"),
+            Duration::minutes(duration),
+            granularity.map(Duration::minutes),
+        )
+    }
+
+    #[test]
+    fn free_slots_should_skip_reserved_windows() {
+        let request = make_request(30, None);
+        request.validate().unwrap();
+        // back to back reservations leave no gap in between
+        let reserved = [
+            (t("08:00"), t("10:00")),
+            (t("10:00"), t("11:00")),
+            (t("12:00"), t("12:20")),
+            (t("14:00"), t("19:00")),
+        ];
+        assert_eq!(
+            slots(request.free_slots(&reserved, 1)),
+            vec![
+                (after("11:00"), before("12:00")),
+                (after("12:20"), before("14:00"))
+            ]
+        );
+    }
+
+    #[test]
+    fn free_slots_should_respect_capacity() {
+        let request = make_request(60, None);
+        let reserved = [(t("09:00"), t("12:00")), (t("11:00"), t("13:00"))];
+        assert_eq!(
+            slots(request.free_slots(&reserved, 2)),
+            vec![(t("09:00"), before("11:00")), (after("12:00"), t("18:00"))]
+        );
+        assert_eq!(
+            slots(request.free_slots(&reserved, 1)),
+            vec![(after("13:00"), t("18:00"))]
+        );
+    }
+
+    #[test]
+    fn free_slots_should_snap_to_granularity() {
+        let request = make_request(30, Some(30));
+        // 09:00 to 09:10 is too short once snapped
+        let reserved = [(t("09:10"), t("10:05")), (t("11:40"), t("17:20"))];
+        assert_eq!(
+            slots(request.free_slots(&reserved, 1)),
+            vec![(t("10:30"), t("11:30")), (t("17:30"), t("18:00"))]
+        );
+    }
+
+    #[test]
+    fn availability_request_should_reject_invalid_duration() {
+        let request = make_request(0, None);
+        assert_eq!(
+            request.validate().unwrap_err(),
+            Error::InvalidDuration("duration".into())
+        );
+        let request = make_request(30, Some(0));
+        assert_eq!(
+            request.validate().unwrap_err(),
+            Error::InvalidDuration("granularity".into())
+        );
+    }
+}
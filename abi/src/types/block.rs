@@ -0,0 +1,158 @@
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::{
+    convert_to_timestamp, BlockRequest, Error, ListBlocksRequest, Reservation, ReservationSeries,
+    ReservationStatus, Validator,
+};
+
+use super::validate_range;
+
+impl BlockRequest {
+    pub fn new(
+        rid: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        reason: impl Into<String>,
+        blocked_by: impl Into<String>,
+    ) -> Self {
+        Self {
+            resource_id: rid.into(),
+            start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
+            end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
+            reason: reason.into(),
+            blocked_by: blocked_by.into(),
+            ..Default::default()
+        }
+    }
+
+    /// repeat the block by the recurrence rule, evaluated in the timezone
+    pub fn with_rrule(mut self, rrule: impl Into<String>, timezone: impl Into<String>) -> Self {
+        self.rrule = rrule.into();
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// cancel the overlapping user reservations instead of failing
+    pub fn cancelling_conflicts(mut self) -> Self {
+        self.cancel_conflicts = true;
+        self
+    }
+
+    /// the series to expand the blocks from, none if it's a single block
+    pub fn series(&self) -> Option<ReservationSeries> {
+        if self.rrule.is_empty() {
+            return None;
+        }
+        let timezone = if self.timezone.is_empty() {
+            "UTC"
+        } else {
+            &self.timezone
+        };
+        Some(ReservationSeries {
+            id: 0,
+            user_id: self.blocked_by.clone(),
+            resource_id: self.resource_id.clone(),
+            rrule: self.rrule.clone(),
+            timezone: timezone.into(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            note: self.reason.clone(),
+            exdates: vec![],
+        })
+    }
+
+    /// the block if it's not recurring
+    pub fn block(&self) -> Reservation {
+        Reservation {
+            user_id: self.blocked_by.clone(),
+            status: ReservationStatus::Blocked as i32,
+            resource_id: self.resource_id.clone(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            note: self.reason.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Validator for BlockRequest {
+    fn validate(&self) -> Result<(), Error> {
+        match self.series() {
+            Some(series) => series.validate(),
+            // the window of the block is validated like a user reservation's
+            None => Reservation {
+                status: ReservationStatus::Pending as i32,
+                ..self.block()
+            }
+            .validate(),
+        }
+    }
+}
+
+impl ListBlocksRequest {
+    pub fn new(rid: impl Into<String>) -> Self {
+        Self {
+            resource_id: rid.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Validator for ListBlocksRequest {
+    fn validate(&self) -> Result<(), Error> {
+        match (self.start.as_ref(), self.end.as_ref()) {
+            (Some(start), Some(end)) => validate_range(Some(start), Some(end)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_request() -> BlockRequest {
+        BlockRequest::new(
+            "lab-1",
+            "2023-03-06T09:00:00-0800".parse().unwrap(),
+            "2023-03-06T12:00:00-0800".parse().unwrap(),
+            "calibration",
+            "admin",
+        )
+    }
+
+    #[test]
+    fn block_request_should_make_a_single_block() {
+        let request = make_request();
+        assert!(request.series().is_none());
+        let block = request.block();
+        assert_eq!(block.status(), ReservationStatus::Blocked);
+        assert_eq!(block.user_id, "admin");
+        assert_eq!(block.note, "calibration");
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn block_request_should_make_a_series_if_recurring() {
+        let request = make_request().with_rrule("FREQ=WEEKLY;COUNT=4", "");
+        let series = request.series().unwrap();
+        assert_eq!(series.timezone, "UTC");
+        assert_eq!(series.expand().unwrap().len(), 4);
+
+        let request = make_request().with_rrule("FREQ=SOMETIMES", "");
+        assert!(matches!(
+            request.validate().unwrap_err(),
+            Error::InvalidRecurrenceRule(_)
+        ));
+    }
+
+    #[test]
+    fn block_request_should_require_who_blocks() {
+        let mut request = make_request();
+        request.blocked_by = "".into();
+        assert_eq!(
+            request.validate().unwrap_err(),
+            Error::InvalidUserId("".into())
+        );
+    }
+}
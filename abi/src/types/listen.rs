@@ -0,0 +1,155 @@
+use crate::{
+    Error, ListenRequest, ReservationChange, ReservationSnapshot, ReservationUpdateType, Validator,
+};
+
+impl ListenRequest {
+    /// check if the change should be sent to the listener
+    pub fn matches(&self, change: &ReservationChange) -> bool {
+        let op = ReservationUpdateType::from(change.op) as i32;
+        if !self.ops.is_empty() && !self.ops.contains(&op) {
+            return false;
+        }
+
+        if self.resource_id.is_empty()
+            && self.user_id.is_empty()
+            && self.resource_pattern.is_empty()
+        {
+            return true;
+        }
+
+        // a change matches if the reservation matches before or after it
+        [change.old.as_ref(), change.new.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|rsvp| self.matches_reservation(rsvp))
+    }
+
+    fn matches_reservation(&self, rsvp: &ReservationSnapshot) -> bool {
+        (self.resource_id.is_empty() || self.resource_id == rsvp.resource_id)
+            && (self.user_id.is_empty() || self.user_id == rsvp.user_id)
+            && (self.resource_pattern.is_empty()
+                || glob_match(&self.resource_pattern, &rsvp.resource_id))
+    }
+}
+
+impl Validator for ListenRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(offset) = self.offset {
+            if offset < 0 {
+                return Err(Error::InvalidCursor(offset));
+            }
+        }
+
+        for op in &self.ops {
+            ReservationUpdateType::from_i32(*op).ok_or(Error::InvalidUpdateType(*op))?;
+        }
+        Ok(())
+    }
+}
+
+/// match s against a glob pattern, `*` matches any characters and `?` matches one character
+fn glob_match(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+    // position of the last `*` in pattern, and the position in s it starts to match
+    let mut star: Option<(usize, usize)> = None;
+
+    while i < s.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == s[i]) {
+            p += 1;
+            i += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, i));
+            p += 1;
+        } else if let Some((sp, si)) = star {
+            // let the last `*` match one more character
+            p = sp + 1;
+            i = si + 1;
+            star = Some((sp, si + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RsvpStatus, RsvpUpdateType};
+
+    #[test]
+    fn glob_match_should_work() {
+        assert!(glob_match("ocean-view-*", "ocean-view-room-417"));
+        assert!(glob_match("*-417", "ocean-view-room-417"));
+        assert!(glob_match("ocean-*-41?", "ocean-view-room-417"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("ocean-view-*", "mountain-view-room-417"));
+        assert!(!glob_match("room-?", "room-417"));
+    }
+
+    #[test]
+    fn listen_request_should_match_changes() {
+        let change = ReservationChange {
+            id: 1,
+            reservation_id: 1,
+            op: RsvpUpdateType::Create,
+            old: None,
+            new: Some(ReservationSnapshot {
+                id: 1,
+                user_id: "alice".into(),
+                status: RsvpStatus::Pending,
+                resource_id: "ocean-view-room-417".into(),
+                start: "2022-12-26T22:00:00Z".parse().unwrap(),
+                end: "2022-12-30T19:00:00Z".parse().unwrap(),
+                note: None,
+                series_id: None,
+                recurrence_id: None,
+                cancel_reason: None,
+                cancelled_at: None,
+                cancelled_by: None,
+                waitlist_id: None,
+            }),
+            timestamp: "2022-12-20T10:00:00Z".parse().unwrap(),
+        };
+
+        let mut request = ListenRequest::default();
+        assert!(request.matches(&change));
+
+        request.resource_pattern = "ocean-view-*".into();
+        request.user_id = "alice".into();
+        assert!(request.matches(&change));
+
+        request.ops = vec![ReservationUpdateType::Delete as i32];
+        assert!(!request.matches(&change));
+
+        request.ops.push(ReservationUpdateType::Create as i32);
+        assert!(request.matches(&change));
+
+        request.resource_id = "ixia-test-1".into();
+        assert!(!request.matches(&change));
+
+        // deleted reservation should be matched by its old state
+        let change = ReservationChange {
+            op: RsvpUpdateType::Delete,
+            old: change.new,
+            new: None,
+            ..change
+        };
+        request.resource_id = "".into();
+        request.ops = vec![];
+        assert!(request.matches(&change));
+    }
+
+    #[test]
+    fn listen_request_should_reject_invalid_op() {
+        let request = ListenRequest {
+            ops: vec![10],
+            ..Default::default()
+        };
+        assert_eq!(request.validate(), Err(Error::InvalidUpdateType(10)));
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{LiveQueryEvent, LiveQueryEventType, Reservation};
+
+impl LiveQueryEvent {
+    pub fn new(
+        event_type: LiveQueryEventType,
+        reservation: Option<Reservation>,
+        offset: i64,
+    ) -> Self {
+        Self {
+            r#type: event_type as i32,
+            reservation,
+            offset,
+        }
+    }
+}
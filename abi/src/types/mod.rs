@@ -6,11 +6,27 @@ use sqlx::postgres::types::PgRange;
 
 use crate::{convert_to_utc_time, Error};
 
+mod availability;
+mod block;
+mod listen;
+mod live_query;
+mod recurrence_rule;
 mod request;
 mod reservation;
+mod reservation_change;
 mod reservation_filter;
+mod reservation_history;
 mod reservation_query;
+mod reservation_series;
 mod reservation_status;
+mod reservation_update_type;
+mod resource;
+mod resource_search;
+mod waitlist;
+
+pub use recurrence_rule::{Frequency, RecurrenceRule, MAX_OCCURRENCES};
+pub use reservation_change::{ReservationChange, ReservationSnapshot};
+pub use resource_search::MAX_SEARCH_LIMIT;
 
 pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
     if start.is_none() || end.is_none() {
@@ -0,0 +1,412 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::Error;
+
+/// max occurrences a recurrence rule could be expanded to
+pub const MAX_OCCURRENCES: usize = 730;
+
+/// max periods (days, weeks or months) scanned when expanding a recurrence rule
+const MAX_PERIODS: u32 = 10_000;
+
+/// max INTERVAL of a recurrence rule, a larger one would step out of the supported dates
+const MAX_INTERVAL: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// the subset of RFC 5545 RRULE supported for recurring reservations:
+/// FREQ (DAILY, WEEKLY, MONTHLY), INTERVAL, COUNT, UNTIL, BYDAY (without ordinals),
+/// BYMONTHDAY (MONTHLY only) and WKST. Either COUNT or UNTIL is required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Vec<u32>,
+    pub week_start: Weekday,
+}
+
+impl RecurrenceRule {
+    /// start times of the occurrences in order, the first one is `start` if it matches the rule
+    pub fn occurrences(&self, start: DateTime<Tz>) -> Vec<DateTime<Tz>> {
+        let tz = start.timezone();
+        let start_date = start.naive_local().date();
+        let time = start.naive_local().time();
+        let limit = self
+            .count
+            .map_or(MAX_OCCURRENCES, |c| (c as usize).min(MAX_OCCURRENCES));
+
+        let mut occurrences = Vec::new();
+        for period in 0..MAX_PERIODS {
+            // the following periods are out of the supported dates as well
+            let dates = match self.period_dates(start_date, period) {
+                Some(v) => v,
+                None => return occurrences,
+            };
+            for date in dates {
+                if date < start_date {
+                    continue;
+                }
+                let occurrence = match localize(&tz, date.and_time(time)) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                if self.until.is_some_and(|until| occurrence > until) {
+                    return occurrences;
+                }
+                occurrences.push(occurrence);
+                if occurrences.len() >= limit {
+                    return occurrences;
+                }
+            }
+        }
+        occurrences
+    }
+
+    /// stop the rule right before the given time, keep the occurrences before it
+    pub fn truncate(&mut self, before: DateTime<Utc>) {
+        self.count = None;
+        self.until = Some(before - Duration::seconds(1));
+    }
+
+    /// candidate dates of the nth period in order, none if the period is out of the supported
+    /// dates
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)? as i64;
+        let dates = match self.freq {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(step))?;
+                if self.by_day.is_empty() || self.by_day.contains(&date.weekday()) {
+                    vec![date]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly => {
+                let week_start = start - Duration::days(self.days_from_week_start(start.weekday()));
+                let week = week_start.checked_add_signed(Duration::weeks(step))?;
+                let mut offsets: Vec<i64> = if self.by_day.is_empty() {
+                    vec![self.days_from_week_start(start.weekday())]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|d| self.days_from_week_start(*d))
+                        .collect()
+                };
+                offsets.sort_unstable();
+                offsets
+                    .into_iter()
+                    .map(|offset| week.checked_add_signed(Duration::days(offset)))
+                    .collect::<Option<_>>()?
+            }
+            Frequency::Monthly => {
+                let months = start.month0() as i64 + step;
+                let year = start.year().checked_add(i32::try_from(months / 12).ok()?)?;
+                // out of the supported years, rather than a month without the day
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                let month = (months % 12) as u32 + 1;
+                let mut days = if self.by_month_day.is_empty() {
+                    vec![start.day()]
+                } else {
+                    self.by_month_day.clone()
+                };
+                days.sort_unstable();
+                // skip the months without the day, e.g. 31st
+                days.into_iter()
+                    .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+                    .collect()
+            }
+        };
+        Some(dates)
+    }
+
+    fn days_from_week_start(&self, day: Weekday) -> i64 {
+        ((7 + day.num_days_from_monday() - self.week_start.num_days_from_monday()) % 7) as i64
+    }
+}
+
+/// local time to the timezone, times in a DST gap are shifted forward by an hour
+fn localize(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    tz.from_local_datetime(&local).earliest().or_else(|| {
+        tz.from_local_datetime(&(local + Duration::hours(1)))
+            .earliest()
+    })
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidRecurrenceRule(s.to_string());
+        let rule = s.trim().trim_start_matches("RRULE:");
+
+        let mut freq = None;
+        let mut ret = Self {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: vec![],
+            by_month_day: vec![],
+            week_start: Weekday::Mon,
+        };
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(invalid)?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid()),
+                    })
+                }
+                "INTERVAL" => ret.interval = value.parse().map_err(|_| invalid())?,
+                "COUNT" => ret.count = Some(value.parse().map_err(|_| invalid())?),
+                "UNTIL" => ret.until = Some(parse_until(value).ok_or_else(invalid)?),
+                "BYDAY" => {
+                    ret.by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?
+                }
+                "BYMONTHDAY" => {
+                    ret.by_month_day = value
+                        .split(',')
+                        .map(|v| v.parse().ok().filter(|d| (1..=31).contains(d)))
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?
+                }
+                "WKST" => ret.week_start = parse_weekday(value).ok_or_else(invalid)?,
+                _ => return Err(invalid()),
+            }
+        }
+
+        ret.freq = freq.ok_or_else(invalid)?;
+        let bounded = ret.count.is_some() != ret.until.is_some();
+        let by_day_allowed = ret.by_day.is_empty() || ret.freq != Frequency::Monthly;
+        let by_month_day_allowed = ret.by_month_day.is_empty() || ret.freq == Frequency::Monthly;
+        let count_allowed = ret
+            .count
+            .is_none_or(|c| c > 0 && c as usize <= MAX_OCCURRENCES);
+        if ret.interval == 0
+            || ret.interval > MAX_INTERVAL
+            || !bounded
+            || !by_day_allowed
+            || !by_month_day_allowed
+            || !count_allowed
+        {
+            return Err(invalid());
+        }
+        Ok(ret)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = match self.freq {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={freq}")?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<_> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<_> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+/// UNTIL in UTC ("20230301T000000Z") or as a date ("20230301", the end of the day in UTC)
+fn parse_until(s: &str) -> Option<DateTime<Utc>> {
+    if let Some(s) = s.strip_suffix('Z') {
+        let dt = NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S").ok()?;
+        return Some(DateTime::from_utc(dt, Utc));
+    }
+    let date = NaiveDate::parse_from_str(s, "%Y%m%d").ok()?;
+    Some(DateTime::from_utc(date.and_hms_opt(23, 59, 59)?, Utc))
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s.to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(tz: Tz, s: &str) -> DateTime<Tz> {
+        tz.from_local_datetime(&NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap())
+            .unwrap()
+    }
+
+    fn format(occurrences: &[DateTime<Tz>]) -> Vec<String> {
+        occurrences
+            .iter()
+            .map(|o| o.format("%Y-%m-%d %H:%M %Z").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rrule_should_parse_and_display() {
+        let rule: RecurrenceRule = "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=4"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.freq, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Wed]);
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=4"
+        );
+
+        let rule: RecurrenceRule = "FREQ=DAILY;UNTIL=20230301T000000Z".parse().unwrap();
+        assert_eq!(rule.to_string(), "FREQ=DAILY;UNTIL=20230301T000000Z");
+    }
+
+    #[test]
+    fn rrule_should_reject_unsupported_rules() {
+        for rule in [
+            "FREQ=WEEKLY",
+            "FREQ=WEEKLY;COUNT=2;UNTIL=20230301T000000Z",
+            "FREQ=YEARLY;COUNT=2",
+            "FREQ=WEEKLY;BYDAY=1MO;COUNT=2",
+            "FREQ=MONTHLY;BYDAY=MO;COUNT=2",
+            "FREQ=DAILY;BYMONTHDAY=1;COUNT=2",
+            "FREQ=DAILY;INTERVAL=0;COUNT=2",
+            "FREQ=DAILY;INTERVAL=100000000;COUNT=2",
+            "FREQ=DAILY;COUNT=1000",
+            "FREQ=DAILY;BYHOUR=1;COUNT=2",
+        ] {
+            assert_eq!(
+                rule.parse::<RecurrenceRule>().unwrap_err(),
+                Error::InvalidRecurrenceRule(rule.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn rrule_should_stop_at_the_end_of_supported_dates() {
+        let tz = chrono_tz::UTC;
+        for freq in [Frequency::Daily, Frequency::Weekly, Frequency::Monthly] {
+            let rule = RecurrenceRule {
+                freq,
+                interval: u32::MAX,
+                count: Some(2),
+                until: None,
+                by_day: vec![],
+                by_month_day: vec![],
+                week_start: Weekday::Mon,
+            };
+            let occurrences = rule.occurrences(local(tz, "2023-03-07 09:00"));
+            assert_eq!(format(&occurrences), vec!["2023-03-07 09:00 UTC"]);
+        }
+    }
+
+    #[test]
+    fn weekly_rule_should_keep_local_time_across_dst() {
+        let tz = chrono_tz::America::Los_Angeles;
+        let rule: RecurrenceRule = "FREQ=WEEKLY;BYDAY=TU,TH;COUNT=5".parse().unwrap();
+        let occurrences = rule.occurrences(local(tz, "2023-03-07 09:00"));
+        assert_eq!(
+            format(&occurrences),
+            vec![
+                "2023-03-07 09:00 PST",
+                "2023-03-09 09:00 PST",
+                "2023-03-14 09:00 PDT",
+                "2023-03-16 09:00 PDT",
+                "2023-03-21 09:00 PDT",
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_rule_should_stop_at_until() {
+        let tz = chrono_tz::UTC;
+        let rule: RecurrenceRule = "FREQ=DAILY;INTERVAL=2;UNTIL=20230107T090000Z"
+            .parse()
+            .unwrap();
+        let occurrences = rule.occurrences(local(tz, "2023-01-01 09:00"));
+        assert_eq!(
+            format(&occurrences),
+            vec![
+                "2023-01-01 09:00 UTC",
+                "2023-01-03 09:00 UTC",
+                "2023-01-05 09:00 UTC",
+                "2023-01-07 09:00 UTC",
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_rule_should_skip_missing_days() {
+        let tz = chrono_tz::UTC;
+        let rule: RecurrenceRule = "FREQ=MONTHLY;COUNT=3".parse().unwrap();
+        let occurrences = rule.occurrences(local(tz, "2023-01-31 09:00"));
+        assert_eq!(
+            format(&occurrences),
+            vec![
+                "2023-01-31 09:00 UTC",
+                "2023-03-31 09:00 UTC",
+                "2023-05-31 09:00 UTC",
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_rule_should_keep_earlier_occurrences() {
+        let tz = chrono_tz::UTC;
+        let mut rule: RecurrenceRule = "FREQ=DAILY;COUNT=10".parse().unwrap();
+        rule.truncate("2023-01-03T09:00:00Z".parse().unwrap());
+        let occurrences = rule.occurrences(local(tz, "2023-01-01 09:00"));
+        assert_eq!(occurrences.len(), 2);
+    }
+}
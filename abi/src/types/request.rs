@@ -1,7 +1,16 @@
 use crate::{
-    CancelRequest, ConfirmRequest, FilterRequest, GetRequest, QueryRequest, Reservation,
-    ReservationFilter, ReservationQuery, ReserveRequest, UpdateRequest,
+    convert_to_timestamp, CancelOccurrenceRequest, CancelRequest, CancelSeriesRequest,
+    ConfirmRequest, CreateResourceRequest, DeleteResourceRequest, FilterRequest, GetRequest,
+    GetResourceRequest, HistoryRequest, LeaveWaitlistRequest, Normalizer, QueryRequest,
+    RescheduleRequest, Reservation, ReservationFilter, ReservationQuery, ReserveBatchRequest,
+    ReserveRequest, Resource, RestoreRequest, UpdateRequest, UpdateResourceRequest, Validator,
+    MAX_SEARCH_LIMIT,
 };
+use chrono::{DateTime, Utc};
+use prost_types::{FieldMask, Timestamp};
+use sqlx::postgres::types::PgRange;
+
+use super::{get_timespan, resource::RESOURCE_UPDATABLE_FIELDS, validate_range};
 
 macro_rules! impl_new {
     ($name: ident, $field: ident, $type: ty) => {
@@ -25,15 +34,411 @@ macro_rules! impl_new {
     };
 }
 
-impl_new!(ReserveRequest, reservation, Reservation);
 impl_new!(FilterRequest, filter, ReservationFilter);
 impl_new!(QueryRequest, query, ReservationQuery);
 impl_new!(ConfirmRequest);
-impl_new!(GetRequest);
-impl_new!(CancelRequest);
+
+impl ReserveRequest {
+    pub fn new(reservation: Reservation) -> Self {
+        Self {
+            reservation: Some(reservation),
+            ..Default::default()
+        }
+    }
+
+    /// suggest up to n free windows if the reservation conflicts
+    pub fn with_suggestions(mut self, n: i32) -> Self {
+        self.suggestions = n;
+        self
+    }
+
+    /// join the waitlist of the window if the reservation conflicts
+    pub fn with_waitlist(mut self) -> Self {
+        self.waitlist = true;
+        self
+    }
+}
+
+impl Validator for ReserveRequest {
+    fn validate(&self) -> Result<(), crate::Error> {
+        // the suggestions are searched like the resources
+        if self.suggestions < 0 || self.suggestions > MAX_SEARCH_LIMIT {
+            return Err(crate::Error::InvalidPageSize(self.suggestions as i64));
+        }
+        match &self.reservation {
+            Some(rsvp) => rsvp.validate_new(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ReserveBatchRequest {
+    pub fn new(reservations: Vec<Reservation>) -> Self {
+        Self { reservations }
+    }
+}
+impl_new!(GetRequest, RestoreRequest);
+impl_new!(HistoryRequest);
+impl_new!(LeaveWaitlistRequest);
+
+impl CancelRequest {
+    pub fn new(id: i64) -> Self {
+        Self {
+            id,
+            reason: String::new(),
+            cancelled_by: String::new(),
+        }
+    }
+
+    /// record why and by whom the reservation is cancelled
+    pub fn with_reason(
+        mut self,
+        reason: impl Into<String>,
+        cancelled_by: impl Into<String>,
+    ) -> Self {
+        self.reason = reason.into();
+        self.cancelled_by = cancelled_by.into();
+        self
+    }
+}
+
+impl CancelOccurrenceRequest {
+    pub fn new(series_id: i64, recurrence_id: DateTime<Utc>) -> Self {
+        Self {
+            series_id,
+            recurrence_id: Some(convert_to_timestamp(&recurrence_id)),
+            ..Default::default()
+        }
+    }
+
+    /// record why and by whom the occurrence is cancelled
+    pub fn with_reason(
+        mut self,
+        reason: impl Into<String>,
+        cancelled_by: impl Into<String>,
+    ) -> Self {
+        self.reason = reason.into();
+        self.cancelled_by = cancelled_by.into();
+        self
+    }
+}
+
+impl Validator for CancelOccurrenceRequest {
+    fn validate(&self) -> Result<(), crate::Error> {
+        self.series_id.validate()?;
+        if self.recurrence_id.is_none() {
+            return Err(crate::Error::InvalidTime);
+        }
+        Ok(())
+    }
+}
+
+impl CancelSeriesRequest {
+    pub fn new(series_id: i64) -> Self {
+        Self {
+            series_id,
+            ..Default::default()
+        }
+    }
+
+    /// record why and by whom the occurrences are cancelled
+    pub fn with_reason(
+        mut self,
+        reason: impl Into<String>,
+        cancelled_by: impl Into<String>,
+    ) -> Self {
+        self.reason = reason.into();
+        self.cancelled_by = cancelled_by.into();
+        self
+    }
+}
+impl_new!(CreateResourceRequest, resource, Resource);
+
+impl GetResourceRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl DeleteResourceRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// reservation fields could be changed by UpdateRequest
+const UPDATABLE_FIELDS: [&str; 5] = ["note", "resource_id", "user_id", "start", "end"];
 
 impl UpdateRequest {
-    pub fn new() -> Self {
-        todo!()
+    pub fn new(id: i64, reservation: Reservation, paths: &[&str]) -> Self {
+        Self {
+            id,
+            note: String::new(),
+            reservation: Some(reservation),
+            update_mask: Some(FieldMask {
+                paths: paths.iter().map(|p| p.to_string()).collect(),
+            }),
+        }
+    }
+
+    /// fields to update, only note if update mask is empty
+    pub fn paths(&self) -> Vec<&str> {
+        match &self.update_mask {
+            Some(mask) if !mask.paths.is_empty() => mask.paths.iter().map(|p| p.as_str()).collect(),
+            _ => vec!["note"],
+        }
+    }
+
+    /// copy the masked fields to the reservation, then validate it
+    pub fn apply(&self, rsvp: &mut Reservation) -> Result<(), crate::Error> {
+        let update_mask = self.update_mask.as_ref().filter(|m| !m.paths.is_empty());
+        let src = match (update_mask, &self.reservation) {
+            (Some(_), Some(src)) => src,
+            // fall back to note only update
+            (None, _) => {
+                rsvp.note = self.note.clone();
+                return rsvp.validate();
+            }
+            (Some(_), None) => return Err(crate::Error::InvalidUpdateMask("reservation".into())),
+        };
+
+        for path in self.paths() {
+            match path {
+                "note" => rsvp.note = src.note.clone(),
+                "resource_id" => rsvp.resource_id = src.resource_id.clone(),
+                "user_id" => rsvp.user_id = src.user_id.clone(),
+                "start" => rsvp.start = src.start.clone(),
+                "end" => rsvp.end = src.end.clone(),
+                _ => return Err(crate::Error::InvalidUpdateMask(path.into())),
+            }
+        }
+        rsvp.validate()
+    }
+}
+
+impl Validator for UpdateRequest {
+    fn validate(&self) -> Result<(), crate::Error> {
+        self.id.validate()?;
+        match self
+            .paths()
+            .into_iter()
+            .find(|p| !UPDATABLE_FIELDS.contains(p))
+        {
+            Some(path) => Err(crate::Error::InvalidUpdateMask(path.into())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl RescheduleRequest {
+    pub fn new(id: i64, start: Timestamp, end: Timestamp) -> Self {
+        Self {
+            id,
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl Validator for RescheduleRequest {
+    fn validate(&self) -> Result<(), crate::Error> {
+        self.id.validate()?;
+        validate_range(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl UpdateResourceRequest {
+    pub fn new(id: impl Into<String>, resource: Resource, paths: &[&str]) -> Self {
+        Self {
+            id: id.into(),
+            resource: Some(resource),
+            update_mask: Some(FieldMask {
+                paths: paths.iter().map(|p| p.to_string()).collect(),
+            }),
+        }
+    }
+
+    /// fields to update, all the updatable fields if update mask is empty
+    pub fn paths(&self) -> Vec<&str> {
+        match &self.update_mask {
+            Some(mask) if !mask.paths.is_empty() => mask.paths.iter().map(|p| p.as_str()).collect(),
+            _ => RESOURCE_UPDATABLE_FIELDS.to_vec(),
+        }
+    }
+
+    /// copy the masked fields to the resource, then normalize it
+    pub fn apply(&self, resource: &mut Resource) -> Result<(), crate::Error> {
+        let src = self
+            .resource
+            .as_ref()
+            .ok_or_else(|| crate::Error::InvalidUpdateMask("resource".into()))?;
+
+        for path in self.paths() {
+            match path {
+                "name" => resource.name = src.name.clone(),
+                "resource_type" => resource.resource_type = src.resource_type.clone(),
+                "tags" => resource.tags = src.tags.clone(),
+                "timezone" => resource.timezone = src.timezone.clone(),
+                "capacity" => resource.capacity = src.capacity,
+                "retired" => resource.retired = src.retired,
+                "buffer_before" => resource.buffer_before = src.buffer_before.clone(),
+                "buffer_after" => resource.buffer_after = src.buffer_after.clone(),
+                _ => return Err(crate::Error::InvalidUpdateMask(path.into())),
+            }
+        }
+        resource.normalize()
+    }
+}
+
+impl Validator for UpdateResourceRequest {
+    fn validate(&self) -> Result<(), crate::Error> {
+        if self.id.is_empty() {
+            return Err(crate::Error::InvalidResourceId(self.id.clone()));
+        }
+        match self
+            .paths()
+            .into_iter()
+            .find(|p| !RESOURCE_UPDATABLE_FIELDS.contains(p))
+        {
+            Some(path) => Err(crate::Error::InvalidUpdateMask(path.into())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_reservation() -> Reservation {
+        Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "hello",
+        )
+    }
+
+    #[test]
+    fn update_request_apply_should_copy_masked_fields() {
+        let mut rsvp = make_reservation();
+        let mut partial = Reservation::new_pending(
+            "bob",
+            "ixia-test-2",
+            "2023-03-25T15:00:00-0700".parse().unwrap(),
+            "2023-04-25T12:00:00-0700".parse().unwrap(),
+            "world",
+        );
+        partial.status = crate::ReservationStatus::Confirmed as i32;
+        let request = UpdateRequest::new(1, partial.clone(), &["resource_id", "start", "end"]);
+        request.validate().unwrap();
+        request.apply(&mut rsvp).unwrap();
+
+        assert_eq!(rsvp.resource_id, partial.resource_id);
+        assert_eq!(rsvp.start, partial.start);
+        assert_eq!(rsvp.end, partial.end);
+        assert_eq!(rsvp.user_id, "alice");
+        assert_eq!(rsvp.note, "hello");
+        assert_eq!(rsvp.status, crate::ReservationStatus::Pending as i32);
+    }
+
+    #[test]
+    fn update_request_without_mask_should_update_note() {
+        let mut rsvp = make_reservation();
+        let request = UpdateRequest {
+            id: 1,
+            note: "world".into(),
+            ..Default::default()
+        };
+        request.validate().unwrap();
+        request.apply(&mut rsvp).unwrap();
+        assert_eq!(rsvp.note, "world");
+    }
+
+    #[test]
+    fn update_request_should_reject_invalid_mask() {
+        let request = UpdateRequest::new(1, make_reservation(), &["note", "status"]);
+        assert_eq!(
+            request.validate().unwrap_err(),
+            crate::Error::InvalidUpdateMask("status".into())
+        );
+    }
+
+    #[test]
+    fn update_request_apply_should_validate_result() {
+        let mut rsvp = make_reservation();
+        let mut partial = make_reservation();
+        partial.user_id = String::new();
+        let request = UpdateRequest::new(1, partial, &["user_id"]);
+        assert_eq!(
+            request.apply(&mut rsvp).unwrap_err(),
+            crate::Error::InvalidUserId(String::new())
+        );
+    }
+
+    #[test]
+    fn update_resource_request_apply_should_copy_masked_fields() {
+        let mut resource = Resource::new("room-417", "Room 417", "room");
+        let mut partial = Resource::new("room-418", "Room 418", "suite");
+        partial.capacity = 4;
+        partial.retired = true;
+        let request = UpdateResourceRequest::new("room-417", partial, &["capacity", "retired"]);
+        request.validate().unwrap();
+        request.apply(&mut resource).unwrap();
+
+        assert_eq!(resource.id, "room-417");
+        assert_eq!(resource.name, "Room 417");
+        assert_eq!(resource.resource_type, "room");
+        assert_eq!(resource.capacity, 4);
+        assert!(resource.retired);
+    }
+
+    #[test]
+    fn reserve_request_should_bound_suggestions() {
+        let request = ReserveRequest::new(make_reservation()).with_suggestions(MAX_SEARCH_LIMIT);
+        request.validate().unwrap();
+        let request = request.with_suggestions(i32::MAX);
+        assert_eq!(
+            request.validate().unwrap_err(),
+            crate::Error::InvalidPageSize(i32::MAX as i64)
+        );
+        let request = request.with_suggestions(-1);
+        assert_eq!(
+            request.validate().unwrap_err(),
+            crate::Error::InvalidPageSize(-1)
+        );
+    }
+
+    #[test]
+    fn reserve_request_should_reject_blocks() {
+        let mut rsvp = make_reservation();
+        rsvp.status = crate::ReservationStatus::Blocked as i32;
+        assert_eq!(
+            ReserveRequest::new(rsvp).validate().unwrap_err(),
+            crate::Error::InvalidStatus(crate::ReservationStatus::Blocked as i32)
+        );
+    }
+
+    #[test]
+    fn update_request_apply_should_keep_cancelled_reservation() {
+        let mut rsvp = make_reservation();
+        rsvp.status = crate::ReservationStatus::Cancelled as i32;
+        let request = UpdateRequest::new(1, make_reservation(), &["user_id"]);
+        request.apply(&mut rsvp).unwrap();
+        assert_eq!(rsvp.status, crate::ReservationStatus::Cancelled as i32);
+    }
+
+    #[test]
+    fn update_resource_request_should_reject_invalid_mask() {
+        let request =
+            UpdateResourceRequest::new("room-417", Resource::new("room-417", "", ""), &["id"]);
+        assert_eq!(
+            request.validate().unwrap_err(),
+            crate::Error::InvalidUpdateMask("id".into())
+        );
     }
 }
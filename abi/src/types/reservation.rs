@@ -26,11 +26,30 @@ impl Reservation {
             start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
             end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
             note: note.into(),
+            series_id: 0,
+            recurrence_id: None,
+            cancel_reason: String::new(),
+            cancelled_at: None,
+            cancelled_by: String::new(),
+            waitlist_id: 0,
         }
     }
     pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
         get_timespan(self.start.as_ref(), self.end.as_ref())
     }
+
+    /// validate a reservation to be made. Blocks are made by the administrative block only, and
+    /// a reservation is cancelled after it's made, so it has passed the capacity check before
+    /// it could be restored
+    pub fn validate_new(&self) -> Result<(), Error> {
+        self.validate()?;
+        match ReservationStatus::from_i32(self.status) {
+            Some(ReservationStatus::Blocked | ReservationStatus::Cancelled) => {
+                Err(Error::InvalidStatus(self.status))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Validator for Reservation {
@@ -42,6 +61,7 @@ impl Validator for Reservation {
             return Err(Error::InvalidResourceId(self.resource_id.clone()));
         }
         validate_range(self.start.as_ref(), self.end.as_ref())?;
+        ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
         Ok(())
     }
 }
@@ -68,13 +88,29 @@ impl FromRow<'_, PgRow> for Reservation {
             start: Some(convert_to_timestamp(&start)),
             end: Some(convert_to_timestamp(&end)),
             note: row.get("note"),
+            series_id: row.get::<Option<i64>, _>("series_id").unwrap_or_default(),
+            recurrence_id: row
+                .get::<Option<DateTime<Utc>>, _>("recurrence_id")
+                .as_ref()
+                .map(convert_to_timestamp),
+            cancel_reason: row
+                .get::<Option<String>, _>("cancel_reason")
+                .unwrap_or_default(),
+            cancelled_at: row
+                .get::<Option<DateTime<Utc>>, _>("cancelled_at")
+                .as_ref()
+                .map(convert_to_timestamp),
+            cancelled_by: row
+                .get::<Option<String>, _>("cancelled_by")
+                .unwrap_or_default(),
+            waitlist_id: row.get::<Option<i64>, _>("waitlist_id").unwrap_or_default(),
         })
     }
 }
 
-struct NaiveRange<T> {
-    start: Option<T>,
-    end: Option<T>,
+pub(crate) struct NaiveRange<T> {
+    pub(crate) start: Option<T>,
+    pub(crate) end: Option<T>,
 }
 
 impl<T> From<PgRange<T>> for NaiveRange<T> {
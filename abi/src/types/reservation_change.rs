@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, types::Json, FromRow, Row};
+
+use crate::{
+    convert_to_timestamp, ListenResponse, Reservation, ReservationStatus, ReservationUpdateType,
+    RsvpStatus, RsvpUpdateType,
+};
+
+/// reservation row image recorded by `rsvp.reservation_snapshot()`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservationSnapshot {
+    pub id: i64,
+    pub user_id: String,
+    pub status: RsvpStatus,
+    pub resource_id: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub series_id: Option<i64>,
+    #[serde(default)]
+    pub recurrence_id: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cancel_reason: Option<String>,
+    #[serde(default)]
+    pub cancelled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cancelled_by: Option<String>,
+    #[serde(default)]
+    pub waitlist_id: Option<i64>,
+}
+
+/// a change recorded in `rsvp.reservation_changes`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservationChange {
+    pub id: i64,
+    pub reservation_id: i64,
+    pub op: RsvpUpdateType,
+    /// reservation before the change, none if op is create
+    pub old: Option<ReservationSnapshot>,
+    /// reservation after the change, none if op is delete
+    pub new: Option<ReservationSnapshot>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<ReservationSnapshot> for Reservation {
+    fn from(snapshot: ReservationSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            user_id: snapshot.user_id,
+            status: ReservationStatus::from(snapshot.status) as i32,
+            resource_id: snapshot.resource_id,
+            start: Some(convert_to_timestamp(&snapshot.start)),
+            end: Some(convert_to_timestamp(&snapshot.end)),
+            note: snapshot.note.unwrap_or_default(),
+            series_id: snapshot.series_id.unwrap_or_default(),
+            recurrence_id: snapshot.recurrence_id.as_ref().map(convert_to_timestamp),
+            cancel_reason: snapshot.cancel_reason.unwrap_or_default(),
+            cancelled_at: snapshot.cancelled_at.as_ref().map(convert_to_timestamp),
+            cancelled_by: snapshot.cancelled_by.unwrap_or_default(),
+            waitlist_id: snapshot.waitlist_id.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<ReservationChange> for ListenResponse {
+    fn from(change: ReservationChange) -> Self {
+        Self {
+            id: change.id,
+            op: ReservationUpdateType::from(change.op) as i32,
+            old: change.old.map(Into::into),
+            new: change.new.map(Into::into),
+            timestamp: Some(convert_to_timestamp(&change.timestamp)),
+        }
+    }
+}
+
+impl ListenResponse {
+    /// latest known state of the reservation, the old one if it's deleted
+    pub fn reservation(&self) -> Option<&Reservation> {
+        self.new.as_ref().or(self.old.as_ref())
+    }
+}
+
+impl FromRow<'_, PgRow> for ReservationChange {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let old: Option<Json<ReservationSnapshot>> = row.try_get("old")?;
+        let new: Option<Json<ReservationSnapshot>> = row.try_get("new")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            reservation_id: row.try_get("reservation_id")?,
+            op: row.try_get("op")?,
+            old: old.map(|v| v.0),
+            new: new.map(|v| v.0),
+            timestamp: row.try_get("create_at")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_should_be_deserialized_from_trigger_json() {
+        let json = r#"{"id": 1, "note": null, "start": "2022-12-26T22:00:00+00:00", "end": "2022-12-30T19:00:00+00:00", "status": "pending", "user_id": "alice", "create_at": "2022-12-20T10:00:00.12345+00:00", "update_at": "2022-12-20T10:00:00.12345+00:00", "resource_id": "ocean-view-room-713"}"#;
+        let snapshot: ReservationSnapshot = serde_json::from_str(json).unwrap();
+        assert_eq!(snapshot.status, RsvpStatus::Pending);
+        assert_eq!(snapshot.start.to_rfc3339(), "2022-12-26T22:00:00+00:00");
+
+        let rsvp = Reservation::from(snapshot);
+        assert_eq!(rsvp.id, 1);
+        assert_eq!(rsvp.resource_id, "ocean-view-room-713");
+        assert_eq!(rsvp.status(), ReservationStatus::Pending);
+        assert_eq!(rsvp.note, "");
+        assert_eq!(rsvp.series_id, 0);
+        assert_eq!(rsvp.recurrence_id, None);
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::VecDeque;
 
 use crate::{
-    Error, FilterPager, Normalizer, Reservation, ReservationFilter, ReservationFilterBuilder,
-    ReservationStatus, ToSql, Validator,
+    convert_to_utc_time, Error, FilterPager, Normalizer, Reservation, ReservationFilter,
+    ReservationFilterBuilder, ReservationStatus, ToSql, Validator,
 };
 
 impl ReservationFilterBuilder {
@@ -147,7 +147,16 @@ impl ToSql for ReservationFilter {
 
         let direction = if self.desc { "DESC" } else { "ASC" };
 
-        format!("SELECT * FROM rsvp.reservations WHERE status = '{status}'::rsvp.reservation_status AND {cursor_cond} AND {user_resource_cond} ORDER BY id {direction} LIMIT {limit}")
+        // rebuild the reservations from their history if asked for a past moment
+        let source = match self.as_of.as_ref() {
+            Some(as_of) => format!(
+                "rsvp.reservations_as_of('{}')",
+                convert_to_utc_time(as_of).to_rfc3339()
+            ),
+            None => "rsvp.reservations".into(),
+        };
+
+        format!("SELECT * FROM {source} WHERE status = '{status}'::rsvp.reservation_status AND {cursor_cond} AND {user_resource_cond} ORDER BY id {direction} LIMIT {limit}")
     }
 }
 
@@ -215,5 +224,21 @@ mod tests {
             sql,
             "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id <= 10 AND user_id = 'tyr' ORDER BY id DESC LIMIT 12"
         );
+
+        let filter = ReservationFilterBuilder::default()
+            .user_id("tyr")
+            .as_of(
+                "2023-01-25T15:00:00-0700"
+                    .parse::<prost_types::Timestamp>()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let sql = filter.to_sql();
+        assert_eq!(
+            sql,
+            "SELECT * FROM rsvp.reservations_as_of('2023-01-25T22:00:00+00:00') WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND user_id = 'tyr' ORDER BY id ASC LIMIT 11"
+        );
     }
 }
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, types::Json, FromRow, Row};
+
+use crate::{
+    convert_to_timestamp, HistoryEntry, ReservationSnapshot, ReservationUpdateType, RsvpUpdateType,
+};
+
+impl FromRow<'_, PgRow> for HistoryEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let op: RsvpUpdateType = row.try_get("op")?;
+        let actor: Option<String> = row.try_get("actor")?;
+        let timestamp: DateTime<Utc> = row.try_get("create_at")?;
+        let snapshot: Json<ReservationSnapshot> = row.try_get("snapshot")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            op: ReservationUpdateType::from(op) as i32,
+            actor: actor.unwrap_or_default(),
+            timestamp: Some(convert_to_timestamp(&timestamp)),
+            reservation: Some(snapshot.0.into()),
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use sqlx::postgres::types::PgRange;
 
-use crate::{ReservationQuery, Validator};
+use crate::{convert_to_utc_time, Reservation, ReservationQuery, Validator};
 
 use super::{get_timespan, validate_range};
 
@@ -9,6 +9,33 @@ impl ReservationQuery {
     pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
         get_timespan(self.start.as_ref(), self.end.as_ref())
     }
+
+    /// check if the reservation is in the result set of the query, same as `rsvp.query()`
+    pub fn matches(&self, rsvp: &Reservation) -> bool {
+        if !self.user_id.is_empty() && self.user_id != rsvp.user_id {
+            return false;
+        }
+        if !self.resource_id.is_empty() && self.resource_id != rsvp.resource_id {
+            return false;
+        }
+        if self.status != rsvp.status {
+            return false;
+        }
+        // the reservation should be within the query range
+        let (start, end) = match (rsvp.start.as_ref(), rsvp.end.as_ref()) {
+            (Some(start), Some(end)) => (convert_to_utc_time(start), convert_to_utc_time(end)),
+            _ => return false,
+        };
+        let after_start = self
+            .start
+            .as_ref()
+            .is_none_or(|v| start >= convert_to_utc_time(v));
+        let before_end = self
+            .end
+            .as_ref()
+            .is_none_or(|v| end <= convert_to_utc_time(v));
+        after_start && before_end
+    }
 }
 
 impl Validator for ReservationQuery {
@@ -23,7 +50,7 @@ mod tests {
 
     use prost_types::Timestamp;
 
-    use crate::convert_to_utc_time;
+    use crate::{ReservationQueryBuilder, ReservationStatus};
 
     use super::*;
 
@@ -68,4 +95,36 @@ mod tests {
         assert_eq!(range.start, Bound::Included(convert_to_utc_time(&start)));
         assert_eq!(range.end, Bound::Included(convert_to_utc_time(&end)));
     }
+
+    #[test]
+    fn query_matches_should_work() {
+        let rsvp = Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "",
+        );
+        let query = ReservationQueryBuilder::default()
+            .user_id("alice")
+            .start("2023-01-01T15:00:00-0700".parse::<Timestamp>().unwrap())
+            .end("2023-03-01T12:00:00-0700".parse::<Timestamp>().unwrap())
+            .status(ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        assert!(query.matches(&rsvp));
+
+        let mut other = query.clone();
+        other.resource_id = "ocean-view-room-417".into();
+        assert!(!other.matches(&rsvp));
+
+        let mut other = query.clone();
+        other.status = ReservationStatus::Confirmed as i32;
+        assert!(!other.matches(&rsvp));
+
+        // the reservation should be fully within the range
+        let mut other = query;
+        other.end = Some("2023-02-01T12:00:00-0700".parse().unwrap());
+        assert!(!other.matches(&rsvp));
+    }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use crate::{
+    convert_to_timestamp, convert_to_utc_time, Error, RecurrenceRule, Reservation,
+    ReservationSeries, ReservationStatus, Validator,
+};
+
+use super::{get_timespan, validate_range};
+
+impl ReservationSeries {
+    pub fn new(
+        uid: impl Into<String>,
+        rid: impl Into<String>,
+        rrule: impl Into<String>,
+        timezone: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            user_id: uid.into(),
+            resource_id: rid.into(),
+            rrule: rrule.into(),
+            timezone: timezone.into(),
+            start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
+            end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
+            note: note.into(),
+            exdates: vec![],
+        }
+    }
+
+    pub fn rule(&self) -> Result<RecurrenceRule, Error> {
+        self.rrule.parse()
+    }
+
+    pub fn tz(&self) -> Result<Tz, Error> {
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidTimezone(self.timezone.clone()))
+    }
+
+    /// timespan of the first occurrence
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+
+    /// pending reservations of all the occurrences, excluded dates are skipped
+    pub fn expand(&self) -> Result<Vec<Reservation>, Error> {
+        self.validate()?;
+        let rule = self.rule()?;
+        let tz = self.tz()?;
+        let start = convert_to_utc_time(self.start.as_ref().unwrap());
+        let duration = convert_to_utc_time(self.end.as_ref().unwrap()) - start;
+        let exdates: HashSet<_> = self.exdates.iter().map(convert_to_utc_time).collect();
+
+        let rsvps = rule
+            .occurrences(start.with_timezone(&tz))
+            .into_iter()
+            .map(|occurrence| occurrence.with_timezone(&Utc))
+            .filter(|occurrence| !exdates.contains(occurrence))
+            .map(|occurrence| Reservation {
+                id: 0,
+                user_id: self.user_id.clone(),
+                status: ReservationStatus::Pending as i32,
+                resource_id: self.resource_id.clone(),
+                start: Some(convert_to_timestamp(&occurrence)),
+                end: Some(convert_to_timestamp(&(occurrence + duration))),
+                note: self.note.clone(),
+                series_id: self.id,
+                recurrence_id: Some(convert_to_timestamp(&occurrence)),
+                ..Default::default()
+            })
+            .collect();
+        Ok(rsvps)
+    }
+}
+
+impl Validator for ReservationSeries {
+    fn validate(&self) -> Result<(), Error> {
+        if self.user_id.is_empty() {
+            return Err(Error::InvalidUserId(self.user_id.clone()));
+        }
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+        self.rule()?;
+        self.tz()?;
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for ReservationSeries {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let timespan: PgRange<DateTime<Utc>> = row.try_get("timespan")?;
+        let exdates: Vec<DateTime<Utc>> = row.try_get("exdates")?;
+        let note: Option<String> = row.try_get("note")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            resource_id: row.try_get("resource_id")?,
+            rrule: row.try_get("rrule")?,
+            timezone: row.try_get("timezone")?,
+            start: bound_to_timestamp(timespan.start),
+            end: bound_to_timestamp(timespan.end),
+            note: note.unwrap_or_default(),
+            exdates: exdates.iter().map(convert_to_timestamp).collect(),
+        })
+    }
+}
+
+fn bound_to_timestamp(bound: std::ops::Bound<DateTime<Utc>>) -> Option<prost_types::Timestamp> {
+    match bound {
+        std::ops::Bound::Included(v) | std::ops::Bound::Excluded(v) => {
+            Some(convert_to_timestamp(&v))
+        }
+        std::ops::Bound::Unbounded => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_series(rrule: &str) -> ReservationSeries {
+        ReservationSeries::new(
+            "alice",
+            "meeting-room-1",
+            rrule,
+            "America/Los_Angeles",
+            "2023-03-06T09:00:00-0800".parse().unwrap(),
+            "2023-03-06T10:00:00-0800".parse().unwrap(),
+            "weekly sync",
+        )
+    }
+
+    #[test]
+    fn series_should_expand_to_reservations() {
+        let mut series = make_series("FREQ=WEEKLY;COUNT=3");
+        series.id = 7;
+        let rsvps = series.expand().unwrap();
+        assert_eq!(rsvps.len(), 3);
+
+        // keeps 9am local time after DST starts on 2023-03-12
+        let second = &rsvps[1];
+        assert_eq!(
+            second.start,
+            Some("2023-03-13T09:00:00-0700".parse().unwrap())
+        );
+        assert_eq!(
+            second.end,
+            Some("2023-03-13T10:00:00-0700".parse().unwrap())
+        );
+        assert_eq!(second.recurrence_id, second.start);
+        assert_eq!(second.series_id, 7);
+        assert_eq!(second.note, "weekly sync");
+    }
+
+    #[test]
+    fn series_should_skip_excluded_dates() {
+        let mut series = make_series("FREQ=WEEKLY;COUNT=3");
+        series.exdates = vec!["2023-03-13T09:00:00-0700".parse().unwrap()];
+        let rsvps = series.expand().unwrap();
+        assert_eq!(rsvps.len(), 2);
+        assert_eq!(
+            rsvps[1].start,
+            Some("2023-03-20T09:00:00-0700".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn series_should_reject_invalid_timezone() {
+        let mut series = make_series("FREQ=WEEKLY;COUNT=3");
+        series.timezone = "Mars/Olympus_Mons".into();
+        assert_eq!(
+            series.validate().unwrap_err(),
+            Error::InvalidTimezone("Mars/Olympus_Mons".into())
+        );
+    }
+}
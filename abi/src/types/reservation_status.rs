@@ -7,6 +7,7 @@ impl From<RsvpStatus> for ReservationStatus {
             RsvpStatus::Pending => ReservationStatus::Pending,
             RsvpStatus::Confirmed => ReservationStatus::Confirmed,
             RsvpStatus::Blocked => ReservationStatus::Blocked,
+            RsvpStatus::Cancelled => ReservationStatus::Cancelled,
         }
     }
 }
@@ -18,6 +19,7 @@ impl std::fmt::Display for ReservationStatus {
             ReservationStatus::Blocked => write!(f, "blocked"),
             ReservationStatus::Confirmed => write!(f, "confirmed"),
             ReservationStatus::Unknown => write!(f, "unknown"),
+            ReservationStatus::Cancelled => write!(f, "cancelled"),
         }
     }
 }
@@ -0,0 +1,12 @@
+use crate::{ReservationUpdateType, RsvpUpdateType};
+
+impl From<RsvpUpdateType> for ReservationUpdateType {
+    fn from(op: RsvpUpdateType) -> Self {
+        match op {
+            RsvpUpdateType::Unknown => ReservationUpdateType::Unknown,
+            RsvpUpdateType::Create => ReservationUpdateType::Create,
+            RsvpUpdateType::Update => ReservationUpdateType::Update,
+            RsvpUpdateType::Delete => ReservationUpdateType::Delete,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::Duration;
+use chrono_tz::Tz;
+use sqlx::{
+    postgres::{types::PgInterval, PgRow},
+    FromRow, Row,
+};
+
+use crate::{convert_to_duration, convert_to_pb_duration, Error, Normalizer, Resource, Validator};
+
+/// resource fields could be changed by UpdateResourceRequest
+pub(super) const RESOURCE_UPDATABLE_FIELDS: [&str; 8] = [
+    "name",
+    "resource_type",
+    "tags",
+    "timezone",
+    "capacity",
+    "retired",
+    "buffer_before",
+    "buffer_after",
+];
+
+/// turnover time longer than a week is surely a mistake
+const MAX_BUFFER_SECONDS: i64 = 7 * 24 * 3600;
+
+impl Resource {
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            resource_type: resource_type.into(),
+            tags: vec![],
+            timezone: "UTC".into(),
+            capacity: 1,
+            retired: false,
+            buffer_before: Some(Default::default()),
+            buffer_after: Some(Default::default()),
+        }
+    }
+
+    /// turnover time needed before and after each reservation
+    pub fn buffers(&self) -> (Duration, Duration) {
+        let to_duration = |d: Option<&prost_types::Duration>| {
+            d.map(convert_to_duration).unwrap_or_else(Duration::zero)
+        };
+        (
+            to_duration(self.buffer_before.as_ref()),
+            to_duration(self.buffer_after.as_ref()),
+        )
+    }
+
+    pub fn tz(&self) -> Result<Tz, Error> {
+        if self.timezone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidTimezone(self.timezone.clone()))
+    }
+}
+
+impl Validator for Resource {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() || self.id.len() > 64 {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+        if self.capacity < 0 {
+            return Err(Error::InvalidCapacity(self.capacity));
+        }
+        // buffers are whole seconds, so they fit in an interval
+        for (name, buffer) in [
+            ("buffer_before", &self.buffer_before),
+            ("buffer_after", &self.buffer_after),
+        ] {
+            match buffer {
+                Some(d) if d.seconds < 0 || d.seconds > MAX_BUFFER_SECONDS || d.nanos != 0 => {
+                    return Err(Error::InvalidDuration(name.into()))
+                }
+                _ => {}
+            }
+        }
+        self.tz()?;
+        Ok(())
+    }
+}
+
+impl Normalizer for Resource {
+    fn do_normalize(&mut self) {
+        if self.name.is_empty() {
+            self.name = self.id.clone();
+        }
+        if self.timezone.is_empty() {
+            self.timezone = "UTC".into();
+        }
+        if self.capacity == 0 {
+            self.capacity = 1;
+        }
+        self.buffer_before.get_or_insert_with(Default::default);
+        self.buffer_after.get_or_insert_with(Default::default);
+        self.tags.sort();
+        self.tags.dedup();
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            resource_type: row.try_get("resource_type")?,
+            tags: row.try_get("tags")?,
+            timezone: row.try_get("timezone")?,
+            capacity: row.try_get("capacity")?,
+            retired: row.try_get("retired")?,
+            buffer_before: Some(interval_to_pb_duration(row.try_get("buffer_before")?)),
+            buffer_after: Some(interval_to_pb_duration(row.try_get("buffer_after")?)),
+        })
+    }
+}
+
+/// buffers are saved as days and microseconds, never months
+fn interval_to_pb_duration(interval: PgInterval) -> prost_types::Duration {
+    let d = Duration::days(interval.days as i64) + Duration::microseconds(interval.microseconds);
+    convert_to_pb_duration(&d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_should_be_normalized() {
+        let mut resource = Resource {
+            id: "ocean-view-room-417".into(),
+            tags: vec!["sea-view".into(), "balcony".into(), "sea-view".into()],
+            ..Default::default()
+        };
+        resource.normalize().unwrap();
+        assert_eq!(resource.name, "ocean-view-room-417");
+        assert_eq!(resource.timezone, "UTC");
+        assert_eq!(resource.capacity, 1);
+        assert_eq!(resource.buffers(), (Duration::zero(), Duration::zero()));
+        assert_eq!(resource.buffer_before, Some(Default::default()));
+        assert_eq!(resource.tags, vec!["balcony", "sea-view"]);
+    }
+
+    #[test]
+    fn resource_should_reject_invalid_fields() {
+        let resource = Resource::new("", "room", "room");
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceId("".into())
+        );
+
+        let mut resource = Resource::new("room-417", "room", "room");
+        resource.capacity = -1;
+        assert_eq!(resource.validate().unwrap_err(), Error::InvalidCapacity(-1));
+
+        resource.capacity = 2;
+        resource.buffer_after = Some(convert_to_pb_duration(&Duration::minutes(-10)));
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidDuration("buffer_after".into())
+        );
+
+        resource.buffer_after = Some(prost_types::Duration {
+            seconds: 600,
+            nanos: 500,
+        });
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidDuration("buffer_after".into())
+        );
+
+        resource.buffer_after = None;
+        resource.timezone = "Mars/Olympus_Mons".into();
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidTimezone("Mars/Olympus_Mons".into())
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use sqlx::postgres::types::PgRange;
+
+use crate::{
+    Error, FindAvailableRequest, Normalizer, Reservation, ReservationStatus, ResourceSearch,
+    ResourceSearchBuilder, Validator,
+};
+
+use super::{get_timespan, validate_range};
+
+/// max resources a search could return
+pub const MAX_SEARCH_LIMIT: i32 = 100;
+
+impl ResourceSearchBuilder {
+    pub fn build(&self) -> Result<ResourceSearch, Error> {
+        let mut search = self
+            .private_build()
+            .expect("failed to build ResourceSearch");
+        search.normalize()?;
+        Ok(search)
+    }
+}
+
+impl ResourceSearch {
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl Validator for ResourceSearch {
+    fn validate(&self) -> Result<(), Error> {
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+        if self.min_capacity < 0 {
+            return Err(Error::InvalidCapacity(self.min_capacity));
+        }
+        if self.limit < 0 || self.limit > MAX_SEARCH_LIMIT {
+            return Err(Error::InvalidPageSize(self.limit as i64));
+        }
+        Ok(())
+    }
+}
+
+impl Normalizer for ResourceSearch {
+    fn do_normalize(&mut self) {
+        if self.limit == 0 {
+            self.limit = 10;
+        }
+        self.tags.sort();
+        self.tags.dedup();
+    }
+}
+
+impl FindAvailableRequest {
+    pub fn new(search: ResourceSearch) -> Self {
+        Self {
+            search: Some(search),
+            ..Default::default()
+        }
+    }
+
+    /// reserve the best candidate for the user
+    pub fn reserve_for(mut self, uid: impl Into<String>, note: impl Into<String>) -> Self {
+        self.reserve = true;
+        self.user_id = uid.into();
+        self.note = note.into();
+        self
+    }
+
+    /// pending reservation of the searched window on the resource
+    pub fn reservation(&self, rid: impl Into<String>) -> Reservation {
+        let search = self.search.as_ref();
+        Reservation {
+            user_id: self.user_id.clone(),
+            status: ReservationStatus::Pending as i32,
+            resource_id: rid.into(),
+            start: search.and_then(|s| s.start.clone()),
+            end: search.and_then(|s| s.end.clone()),
+            note: self.note.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Validator for FindAvailableRequest {
+    fn validate(&self) -> Result<(), Error> {
+        match &self.search {
+            Some(search) => search.validate()?,
+            None => return Err(Error::InvalidTime),
+        }
+        if self.reserve && self.user_id.is_empty() {
+            return Err(Error::InvalidUserId(self.user_id.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_types::Timestamp;
+
+    use super::*;
+
+    fn make_search() -> ResourceSearch {
+        ResourceSearchBuilder::default()
+            .tags(vec!["projector".to_string(), "projector".to_string()])
+            .min_capacity(8)
+            .start("2023-01-25T14:00:00-0700".parse::<Timestamp>().unwrap())
+            .end("2023-01-25T15:00:00-0700".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn resource_search_should_be_normalized() {
+        let search = make_search();
+        assert_eq!(search.limit, 10);
+        assert_eq!(search.tags, vec!["projector"]);
+    }
+
+    #[test]
+    fn find_available_request_should_need_user_to_reserve() {
+        let request = FindAvailableRequest::new(make_search());
+        request.validate().unwrap();
+
+        let request = request.reserve_for("", "");
+        assert_eq!(
+            request.validate().unwrap_err(),
+            Error::InvalidUserId("".into())
+        );
+
+        let request = request.reserve_for("alice", "design review");
+        request.validate().unwrap();
+        let rsvp = request.reservation("meeting-room-1");
+        rsvp.validate().unwrap();
+        assert_eq!(rsvp.start, make_search().start);
+        assert_eq!(rsvp.note, "design review");
+    }
+}
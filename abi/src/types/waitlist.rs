@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use crate::{convert_to_timestamp, ListWaitlistRequest, WaitlistEntry};
+
+use super::reservation::NaiveRange;
+
+impl ListWaitlistRequest {
+    pub fn new(rid: impl Into<String>) -> Self {
+        Self {
+            resource_id: rid.into(),
+        }
+    }
+}
+
+impl FromRow<'_, PgRow> for WaitlistEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let timespan: PgRange<DateTime<Utc>> = row.try_get("timespan")?;
+        let range: NaiveRange<DateTime<Utc>> = timespan.into();
+        let joined_at: DateTime<Utc> = row.try_get("create_at")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            resource_id: row.try_get("resource_id")?,
+            start: range.start.as_ref().map(convert_to_timestamp),
+            end: range.end.as_ref().map(convert_to_timestamp),
+            note: row
+                .try_get::<Option<String>, _>("note")?
+                .unwrap_or_default(),
+            reservation_id: row
+                .try_get::<Option<i64>, _>("reservation_id")?
+                .unwrap_or_default(),
+            joined_at: Some(convert_to_timestamp(&joined_at)),
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use prost_types::Timestamp;
 
 pub fn convert_to_utc_time(ts: &Timestamp) -> DateTime<Utc> {
@@ -11,6 +11,19 @@ pub fn convert_to_utc_time(ts: &Timestamp) -> DateTime<Utc> {
 pub fn convert_to_timestamp(dt: &DateTime<Utc>) -> Timestamp {
     Timestamp {
         seconds: dt.timestamp(),
-        nanos: dt.timestamp_subset_nanos() as _,
+        nanos: dt.timestamp_subsec_nanos() as _,
+    }
+}
+
+pub fn convert_to_duration(d: &prost_types::Duration) -> Duration {
+    Duration::seconds(d.seconds) + Duration::nanoseconds(d.nanos as i64)
+}
+
+pub fn convert_to_pb_duration(d: &Duration) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: d.num_seconds(),
+        nanos: (*d - Duration::seconds(d.num_seconds()))
+            .num_nanoseconds()
+            .unwrap_or_default() as i32,
     }
 }
@@ -0,0 +1 @@
+DROP TABLE rsvp.consumer_groups;
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- consumer groups of the reservation change queue, each keeps its own acknowledged offset
+CREATE TABLE rsvp.consumer_groups (
+    name VARCHAR(64) NOT NULL,
+    acked_id BIGINT NOT NULL DEFAULT 0,
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+    update_at timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT consumer_groups_pkey PRIMARY KEY (name)
+);
@@ -0,0 +1,34 @@
+-- record the reservation row images and the time of each change
+ALTER TABLE rsvp.reservation_changes
+    ADD COLUMN old JSONB,
+    ADD COLUMN new JSONB,
+    ADD COLUMN create_at timestamp with time zone NOT NULL DEFAULT now();
+
+-- snapshot of a reservation, timespan is flattened to start/end timestamps
+CREATE OR REPLACE FUNCTION rsvp.reservation_snapshot(r rsvp.reservations) RETURNS JSONB AS $$
+    SELECT (to_jsonb(r) - 'timespan') || jsonb_build_object('start', lower(r.timespan), 'end', upper(r.timespan));
+$$ LANGUAGE sql IMMUTABLE;
+
+-- trigger for add/update/delete a reservation
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status changed, update reservation_changes
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
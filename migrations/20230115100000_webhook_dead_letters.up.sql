@@ -0,0 +1,14 @@
+-- reservation changes that could not be delivered to a webhook
+CREATE TABLE rsvp.webhook_dead_letters (
+    id BIGSERIAL NOT NULL,
+    webhook VARCHAR(64) NOT NULL,
+    url TEXT NOT NULL,
+    change_id BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    error TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT webhook_dead_letters_pkey PRIMARY KEY (id)
+);
+CREATE INDEX webhook_dead_letters_webhook_idx ON rsvp.webhook_dead_letters (webhook);
@@ -0,0 +1,3 @@
+DROP INDEX rsvp.reservations_series_occurrence_idx;
+ALTER TABLE rsvp.reservations DROP COLUMN series_id, DROP COLUMN recurrence_id;
+DROP TABLE rsvp.reservation_series;
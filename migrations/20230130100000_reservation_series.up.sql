@@ -0,0 +1,25 @@
+-- recurring reservations, expanded to concrete rows in rsvp.reservations
+CREATE TABLE rsvp.reservation_series (
+    id BIGSERIAL NOT NULL,
+    user_id VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    -- RFC 5545 recurrence rule, evaluated in the timezone
+    rrule TEXT NOT NULL,
+    timezone VARCHAR(64) NOT NULL,
+    -- the first occurrence
+    timespan TSTZRANGE NOT NULL,
+    note TEXT,
+    -- start times of the excluded occurrences
+    exdates TIMESTAMPTZ[] NOT NULL DEFAULT '{}',
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+    update_at timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT reservation_series_pkey PRIMARY KEY (id)
+);
+
+-- occurrences keep their original start time, so a rescheduled one could still be identified
+ALTER TABLE rsvp.reservations
+    ADD COLUMN series_id BIGINT REFERENCES rsvp.reservation_series (id) ON DELETE SET NULL,
+    ADD COLUMN recurrence_id TIMESTAMPTZ;
+
+CREATE UNIQUE INDEX reservations_series_occurrence_idx ON rsvp.reservations (series_id, recurrence_id);
@@ -0,0 +1,3 @@
+ALTER TABLE rsvp.reservation_series DROP CONSTRAINT reservation_series_resource_id_fkey;
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_resource_id_fkey;
+DROP TABLE rsvp.resources;
@@ -0,0 +1,28 @@
+-- catalog of the resources which could be reserved
+CREATE TABLE rsvp.resources (
+    id VARCHAR(64) NOT NULL,
+    name TEXT NOT NULL,
+    resource_type VARCHAR(64) NOT NULL DEFAULT '',
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    capacity INTEGER NOT NULL DEFAULT 1,
+    -- retired resources are kept for the existing reservations, but can't be reserved
+    retired BOOLEAN NOT NULL DEFAULT false,
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+    update_at timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT resources_pkey PRIMARY KEY (id),
+    CONSTRAINT resources_capacity_check CHECK (capacity > 0)
+);
+CREATE INDEX resources_tags_idx ON rsvp.resources USING gin (tags);
+
+-- resources were only known by their reservations so far
+INSERT INTO rsvp.resources (id, name)
+    SELECT resource_id, resource_id FROM rsvp.reservations
+    UNION
+    SELECT resource_id, resource_id FROM rsvp.reservation_series;
+
+ALTER TABLE rsvp.reservations
+    ADD CONSTRAINT reservations_resource_id_fkey FOREIGN KEY (resource_id) REFERENCES rsvp.resources (id);
+ALTER TABLE rsvp.reservation_series
+    ADD CONSTRAINT reservation_series_resource_id_fkey FOREIGN KEY (resource_id) REFERENCES rsvp.resources (id);
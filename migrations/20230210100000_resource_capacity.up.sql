@@ -0,0 +1,53 @@
+-- a resource could hold up to its capacity of overlapping reservations, checked by a trigger
+-- instead of the exclusion constraint
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+CREATE INDEX reservations_timespan_idx ON rsvp.reservations USING gist (resource_id, timespan);
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _capacity INTEGER;
+    _at TIMESTAMPTZ;
+    _taken BIGINT[];
+    _old rsvp.reservations;
+BEGIN
+    -- serialize the checks on the same resource, the lock is held until the transaction ends
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(NEW.resource_id));
+
+    SELECT capacity INTO _capacity FROM rsvp.resources WHERE id = NEW.resource_id;
+    _capacity := COALESCE(_capacity, 1);
+
+    -- the most seats are taken at the start of one of the overlapping reservations
+    SELECT p.at, array_agg(r.id ORDER BY r.id) INTO _at, _taken
+        FROM (
+            SELECT DISTINCT GREATEST(lower(NEW.timespan), lower(timespan)) AS at
+            FROM rsvp.reservations
+            WHERE resource_id = NEW.resource_id AND id <> NEW.id AND timespan && NEW.timespan
+        ) p
+        JOIN rsvp.reservations r
+            ON r.resource_id = NEW.resource_id AND r.id <> NEW.id AND r.timespan @> p.at
+        GROUP BY p.at
+        ORDER BY count(*) DESC, p.at
+        LIMIT 1;
+
+    IF array_length(_taken, 1) >= _capacity THEN
+        SELECT * INTO _old FROM rsvp.reservations WHERE id = _taken[1];
+        -- keep the detail of the exclusion constraint, so the conflict could still be parsed
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            MESSAGE = 'conflicting key value violates exclusion constraint "reservations_conflict"',
+            DETAIL = format(
+                'Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s). Taken %s of capacity %s by reservations (%s).',
+                NEW.resource_id, NEW.timespan, _old.resource_id, _old.timespan,
+                array_length(_taken, 1), _capacity, array_to_string(_taken, ', ')
+            ),
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            CONSTRAINT = 'reservations_conflict';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservations_capacity_trigger
+    BEFORE INSERT OR UPDATE OF resource_id, timespan ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_capacity_trigger();
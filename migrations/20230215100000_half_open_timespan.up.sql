@@ -0,0 +1,9 @@
+-- timespans exclude their end, so a reservation could start right when another ends.
+-- It's only a change of representation, so the triggers don't record it
+ALTER TABLE rsvp.reservations DISABLE TRIGGER USER;
+UPDATE rsvp.reservations SET timespan = tstzrange(lower(timespan), upper(timespan), '[)')
+    WHERE upper_inc(timespan);
+ALTER TABLE rsvp.reservations ENABLE TRIGGER USER;
+
+UPDATE rsvp.reservation_series SET timespan = tstzrange(lower(timespan), upper(timespan), '[)')
+    WHERE upper_inc(timespan);
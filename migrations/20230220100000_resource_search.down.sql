@@ -0,0 +1 @@
+DROP FUNCTION rsvp.seats_taken(text, TSTZRANGE);
@@ -0,0 +1,14 @@
+-- most reservations overlapping each other on the resource within the window
+CREATE OR REPLACE FUNCTION rsvp.seats_taken(rid text, during TSTZRANGE) RETURNS integer AS $$
+    SELECT COALESCE(max(taken), 0)::integer FROM (
+        -- the most seats are taken at the start of one of the overlapping reservations
+        SELECT count(*) AS taken
+        FROM (
+            SELECT DISTINCT GREATEST(lower(during), lower(timespan)) AS at
+            FROM rsvp.reservations
+            WHERE resource_id = rid AND timespan && during
+        ) p
+        JOIN rsvp.reservations r ON r.resource_id = rid AND r.timespan @> p.at
+        GROUP BY p.at
+    ) t;
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,2 @@
+-- postgres can't drop a value from an enum, 'cancelled' is kept but no longer used
+SELECT 1;
@@ -0,0 +1,2 @@
+-- a new enum value can't be used in the transaction adding it, so it's added on its own
+ALTER TYPE rsvp.reservation_status ADD VALUE 'cancelled';
@@ -0,0 +1,26 @@
+-- trigger for add/update/delete a reservation
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if any field changed, update reservation_changes
+        IF OLD IS DISTINCT FROM NEW THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TABLE rsvp.reservation_history;
+DROP FUNCTION rsvp.reservation_history_append_only();
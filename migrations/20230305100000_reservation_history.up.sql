@@ -0,0 +1,64 @@
+-- append-only audit trail of every reservation change, kept even after the reservation is
+-- deleted and never pruned like reservation_changes
+CREATE TABLE rsvp.reservation_history (
+    id BIGSERIAL NOT NULL,
+    reservation_id BIGINT NOT NULL,
+    op rsvp.reservation_update_type NOT NULL,
+    -- who made the change, set by `SET LOCAL rsvp.actor`
+    actor VARCHAR(64),
+    -- reservation after the change, or before it if deleted
+    snapshot JSONB NOT NULL,
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+
+    CONSTRAINT reservation_history_pkey PRIMARY KEY (id)
+);
+CREATE INDEX reservation_history_reservation_id_idx ON rsvp.reservation_history (reservation_id, id);
+
+CREATE OR REPLACE FUNCTION rsvp.reservation_history_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'reservation history is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservation_history_append_only
+    BEFORE UPDATE OR DELETE ON rsvp.reservation_history
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.reservation_history_append_only();
+
+-- the changes not pruned yet are the best known history so far
+INSERT INTO rsvp.reservation_history (reservation_id, op, snapshot, create_at)
+    SELECT reservation_id, op, COALESCE(new, old), create_at
+    FROM rsvp.reservation_changes
+    WHERE COALESCE(new, old) IS NOT NULL
+    ORDER BY id;
+
+-- trigger for add/update/delete a reservation
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _actor VARCHAR(64) := NULLIF(current_setting('rsvp.actor', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (NEW.id, 'create', _actor, rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if any field changed, update reservation_changes
+        IF OLD IS DISTINCT FROM NEW THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+            INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+                VALUES (NEW.id, 'update', _actor, rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (OLD.id, 'delete', _actor, rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,67 @@
+-- reservations as they were at the moment, rebuilt from the latest history entry of each one
+CREATE OR REPLACE FUNCTION rsvp.reservations_as_of(_at TIMESTAMPTZ) RETURNS SETOF rsvp.reservations AS $$
+    SELECT (jsonb_populate_record(
+        NULL::rsvp.reservations,
+        -- the snapshot flattens the timespan to start/end
+        h.snapshot || jsonb_build_object('timespan', tstzrange(
+            (h.snapshot->>'start')::timestamptz,
+            (h.snapshot->>'end')::timestamptz
+        ))
+    )).*
+    FROM (
+        SELECT DISTINCT ON (reservation_id) op, snapshot
+        FROM rsvp.reservation_history
+        WHERE create_at <= _at
+        ORDER BY reservation_id, id DESC
+    ) h
+    WHERE h.op <> 'delete';
+$$ LANGUAGE sql STABLE;
+
+-- same as before, but query the reservations as of the moment if given
+DROP FUNCTION rsvp.query;
+CREATE OR REPLACE FUNCTION rsvp.query(
+    uid text,
+    rid text,
+    _start timestamp with time zone,
+    _end timestamp with time zone,
+    status rsvp.reservation_status DEFAULT 'pending',
+    is_desc bool DEFAULT FALSE,
+    as_of timestamp with time zone DEFAULT NULL
+) RETURNS TABLE (LIKE rsvp.reservations) AS $$
+DECLARE
+    _during TSTZRANGE;
+    _sql text;
+BEGIN
+    -- if start or end is null, use infinity
+    _during := TSTZRANGE(
+        COALESCE(_start, '-infinity'),
+        COALESCE(_end, 'infinity'),
+        '[)'
+    );
+
+    -- format the query based on parameters
+    _sql := format('SELECT * FROM %s WHERE %L @> timespan AND status = %L AND %s ORDER BY lower(timespan) %s',
+        CASE
+            WHEN as_of IS NULL THEN 'rsvp.reservations'
+            ELSE format('rsvp.reservations_as_of(%L)', as_of)
+        END,
+        _during,
+        status,
+        CASE
+            WHEN uid IS NULL AND rid IS NULL THEN 'TRUE'
+            WHEN uid IS NULL THEN 'resource_id = ' || quote_literal(rid)
+            WHEN rid IS NULL THEN 'user_id = ' || quote_literal(uid)
+            ELSE 'user_id = ' || quote_literal(uid) || ' AND resource_id = ' || quote_literal(rid)
+        END,
+        CASE
+            WHEN is_desc THEN 'DESC'
+            ELSE 'ASC'
+        END
+    );
+    -- log the sql
+    RAISE NOTICE '%', _sql;
+
+    -- excute the query
+    RETURN QUERY EXECUTE _sql;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,95 @@
+-- administrative blocks take the whole resource, whatever its capacity is
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _capacity INTEGER;
+    _before INTERVAL;
+    _after INTERVAL;
+    _span TSTZRANGE;
+    _need INTEGER;
+    _at TIMESTAMPTZ;
+    _seats BIGINT;
+    _taken BIGINT[];
+    _old rsvp.reservations;
+BEGIN
+    -- cancelled reservations take no seat, and an active one keeping its window is already
+    -- checked
+    IF NEW.status = 'cancelled' OR (TG_OP = 'UPDATE' AND OLD.status <> 'cancelled'
+        AND OLD.resource_id = NEW.resource_id AND OLD.timespan = NEW.timespan) THEN
+        RETURN NEW;
+    END IF;
+
+    -- serialize the checks on the same resource, the lock is held until the transaction ends
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(NEW.resource_id));
+
+    SELECT capacity, buffer_before, buffer_after INTO _capacity, _before, _after
+        FROM rsvp.resources WHERE id = NEW.resource_id;
+    _capacity := COALESCE(_capacity, 1);
+    _before := COALESCE(_before, '0');
+    _after := COALESCE(_after, '0');
+    _span := tstzrange(lower(NEW.timespan) - _before, upper(NEW.timespan) + _after);
+    -- a block takes all the seats of the resource
+    _need := CASE WHEN NEW.status = 'blocked' THEN _capacity ELSE 1 END;
+
+    -- the most seats are taken at the start of one of the overlapping buffered windows
+    WITH b AS (
+        SELECT id, tstzrange(lower(timespan) - _before, upper(timespan) + _after) AS span,
+            CASE WHEN status = 'blocked' THEN _capacity ELSE 1 END AS seats
+        FROM rsvp.reservations
+        WHERE resource_id = NEW.resource_id AND id <> NEW.id AND status <> 'cancelled'
+            AND timespan && tstzrange(lower(_span) - _after, upper(_span) + _before)
+    )
+    SELECT p.at, sum(b.seats), array_agg(b.id ORDER BY b.id) INTO _at, _seats, _taken
+        FROM (
+            SELECT DISTINCT GREATEST(lower(_span), lower(span)) AS at FROM b WHERE span && _span
+        ) p
+        JOIN b ON b.span @> p.at
+        GROUP BY p.at
+        ORDER BY sum(b.seats) DESC, p.at
+        LIMIT 1;
+
+    IF _seats + _need > _capacity THEN
+        SELECT * INTO _old FROM rsvp.reservations WHERE id = _taken[1];
+        -- keep the detail of the exclusion constraint, so the conflict could still be parsed.
+        -- The windows are reported with their buffers
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            MESSAGE = 'conflicting key value violates exclusion constraint "reservations_conflict"',
+            DETAIL = format(
+                'Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s). Taken %s of capacity %s by reservations (%s).',
+                NEW.resource_id, _span, _old.resource_id,
+                tstzrange(lower(_old.timespan) - _before, upper(_old.timespan) + _after),
+                _seats, _capacity, array_to_string(_taken, ', ')
+            ),
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            CONSTRAINT = 'reservations_conflict';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- most buffered windows of the active reservations overlapping each other on the resource,
+-- when reserving the window. A block takes all the seats
+CREATE OR REPLACE FUNCTION rsvp.seats_taken(rid text, during TSTZRANGE) RETURNS integer AS $$
+    WITH s AS (
+        SELECT tstzrange(lower(during) - buffer_before, upper(during) + buffer_after) AS span,
+            buffer_before, buffer_after, capacity
+        FROM rsvp.resources WHERE id = rid
+    ), b AS (
+        SELECT tstzrange(lower(r.timespan) - s.buffer_before, upper(r.timespan) + s.buffer_after) AS span,
+            CASE WHEN r.status = 'blocked' THEN s.capacity ELSE 1 END AS seats
+        FROM rsvp.reservations r, s
+        WHERE r.resource_id = rid AND r.status <> 'cancelled'
+            AND r.timespan && tstzrange(lower(s.span) - s.buffer_after, upper(s.span) + s.buffer_before)
+    )
+    SELECT COALESCE(max(taken), 0)::integer FROM (
+        -- the most seats are taken at the start of one of the overlapping buffered windows
+        SELECT sum(b.seats) AS taken
+        FROM (
+            SELECT DISTINCT GREATEST(lower(s.span), lower(b.span)) AS at FROM b, s WHERE b.span && s.span
+        ) p
+        JOIN b ON b.span @> p.at
+        GROUP BY p.at
+    ) t;
+$$ LANGUAGE sql STABLE;
+
@@ -0,0 +1,71 @@
+-- requests waiting for their windows on the resources, promoted to pending reservations in the
+-- order they joined once the windows are free
+CREATE TABLE rsvp.waitlist (
+    id BIGSERIAL NOT NULL,
+    user_id VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    timespan TSTZRANGE NOT NULL,
+    note TEXT,
+    -- reservation the entry is promoted to, null while waiting
+    reservation_id BIGINT,
+    create_at timestamp with time zone NOT NULL DEFAULT now(),
+    promoted_at timestamp with time zone,
+
+    CONSTRAINT waitlist_pkey PRIMARY KEY (id)
+);
+CREATE INDEX waitlist_waiting_idx ON rsvp.waitlist (resource_id, id) WHERE reservation_id IS NULL;
+
+-- promote the waiting entries overlapping the freed window, first come first served. The ones
+-- still conflicting keep waiting
+CREATE OR REPLACE FUNCTION rsvp.promote_waitlist(rid text, during TSTZRANGE) RETURNS void AS $$
+DECLARE
+    _gap INTERVAL;
+    _entry rsvp.waitlist;
+    _id BIGINT;
+BEGIN
+    -- same lock as the capacity trigger, so the entries are promoted in order
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(rid));
+
+    -- retired resources can't be reserved
+    IF EXISTS (SELECT 1 FROM rsvp.resources WHERE id = rid AND retired) THEN
+        RETURN;
+    END IF;
+    SELECT buffer_before + buffer_after INTO _gap FROM rsvp.resources WHERE id = rid;
+    _gap := COALESCE(_gap, '0');
+
+    FOR _entry IN
+        SELECT * FROM rsvp.waitlist
+        WHERE resource_id = rid AND reservation_id IS NULL
+            AND timespan && tstzrange(lower(during) - _gap, upper(during) + _gap)
+        ORDER BY id
+    LOOP
+        BEGIN
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note)
+                VALUES (_entry.user_id, _entry.resource_id, _entry.timespan, _entry.note)
+                RETURNING id INTO _id;
+        EXCEPTION WHEN exclusion_violation THEN
+            CONTINUE;
+        END;
+        UPDATE rsvp.waitlist SET reservation_id = _id, promoted_at = now() WHERE id = _entry.id;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+-- an active reservation leaving its window frees its seats for the waitlist
+CREATE OR REPLACE FUNCTION rsvp.waitlist_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.status = 'cancelled' OR (TG_OP = 'UPDATE' AND NEW.status <> 'cancelled'
+        AND OLD.resource_id = NEW.resource_id AND OLD.timespan = NEW.timespan) THEN
+        RETURN NULL;
+    END IF;
+    PERFORM rsvp.promote_waitlist(OLD.resource_id, OLD.timespan);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- deferred to the commit, so the freed window isn't given away before the transaction has
+-- taken what it needs, e.g. a block cancelling the reservations in its window
+CREATE CONSTRAINT TRIGGER waitlist_trigger
+    AFTER UPDATE OF resource_id, timespan, status OR DELETE ON rsvp.reservations
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.waitlist_trigger();
@@ -0,0 +1,135 @@
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _actor VARCHAR(64) := NULLIF(current_setting('rsvp.actor', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (NEW.id, 'create', _actor, rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if any field changed, update reservation_changes
+        IF OLD IS DISTINCT FROM NEW THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+            INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+                VALUES (NEW.id, 'update', _actor, rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (OLD.id, 'delete', _actor, rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _capacity INTEGER;
+    _before INTERVAL;
+    _after INTERVAL;
+    _span TSTZRANGE;
+    _need INTEGER;
+    _at TIMESTAMPTZ;
+    _seats BIGINT;
+    _taken BIGINT[];
+    _old rsvp.reservations;
+BEGIN
+    -- cancelled reservations take no seat, and an active one keeping its window is already
+    -- checked
+    IF NEW.status = 'cancelled' OR (TG_OP = 'UPDATE' AND OLD.status <> 'cancelled'
+        AND OLD.resource_id = NEW.resource_id AND OLD.timespan = NEW.timespan) THEN
+        RETURN NEW;
+    END IF;
+
+    -- serialize the checks on the same resource, the lock is held until the transaction ends
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(NEW.resource_id));
+
+    SELECT capacity, buffer_before, buffer_after INTO _capacity, _before, _after
+        FROM rsvp.resources WHERE id = NEW.resource_id;
+    _capacity := COALESCE(_capacity, 1);
+    _before := COALESCE(_before, '0');
+    _after := COALESCE(_after, '0');
+    _span := tstzrange(lower(NEW.timespan) - _before, upper(NEW.timespan) + _after);
+    -- a block takes all the seats of the resource
+    _need := CASE WHEN NEW.status = 'blocked' THEN _capacity ELSE 1 END;
+
+    -- the most seats are taken at the start of one of the overlapping buffered windows
+    WITH b AS (
+        SELECT id, tstzrange(lower(timespan) - _before, upper(timespan) + _after) AS span,
+            CASE WHEN status = 'blocked' THEN _capacity ELSE 1 END AS seats
+        FROM rsvp.reservations
+        WHERE resource_id = NEW.resource_id AND id <> NEW.id AND status <> 'cancelled'
+            AND timespan && tstzrange(lower(_span) - _after, upper(_span) + _before)
+    )
+    SELECT p.at, sum(b.seats), array_agg(b.id ORDER BY b.id) INTO _at, _seats, _taken
+        FROM (
+            SELECT DISTINCT GREATEST(lower(_span), lower(span)) AS at FROM b WHERE span && _span
+        ) p
+        JOIN b ON b.span @> p.at
+        GROUP BY p.at
+        ORDER BY sum(b.seats) DESC, p.at
+        LIMIT 1;
+
+    IF _seats + _need > _capacity THEN
+        SELECT * INTO _old FROM rsvp.reservations WHERE id = _taken[1];
+        -- keep the detail of the exclusion constraint, so the conflict could still be parsed.
+        -- The windows are reported with their buffers
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            MESSAGE = 'conflicting key value violates exclusion constraint "reservations_conflict"',
+            DETAIL = format(
+                'Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s). Taken %s of capacity %s by reservations (%s).',
+                NEW.resource_id, _span, _old.resource_id,
+                tstzrange(lower(_old.timespan) - _before, upper(_old.timespan) + _after),
+                _seats, _capacity, array_to_string(_taken, ', ')
+            ),
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            CONSTRAINT = 'reservations_conflict';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION rsvp.promote_waitlist(rid text, during TSTZRANGE) RETURNS void AS $$
+DECLARE
+    _gap INTERVAL;
+    _entry rsvp.waitlist;
+    _id BIGINT;
+BEGIN
+    -- same lock as the capacity trigger, so the entries are promoted in order
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(rid));
+
+    -- retired resources can't be reserved
+    IF EXISTS (SELECT 1 FROM rsvp.resources WHERE id = rid AND retired) THEN
+        RETURN;
+    END IF;
+    SELECT buffer_before + buffer_after INTO _gap FROM rsvp.resources WHERE id = rid;
+    _gap := COALESCE(_gap, '0');
+
+    FOR _entry IN
+        SELECT * FROM rsvp.waitlist
+        WHERE resource_id = rid AND reservation_id IS NULL
+            AND timespan && tstzrange(lower(during) - _gap, upper(during) + _gap)
+        ORDER BY id
+    LOOP
+        BEGIN
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note)
+                VALUES (_entry.user_id, _entry.resource_id, _entry.timespan, _entry.note)
+                RETURNING id INTO _id;
+        EXCEPTION WHEN exclusion_violation THEN
+            CONTINUE;
+        END;
+        UPDATE rsvp.waitlist SET reservation_id = _id, promoted_at = now() WHERE id = _entry.id;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION rsvp.lock_resource(text);
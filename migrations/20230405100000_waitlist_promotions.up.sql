@@ -0,0 +1,47 @@
+-- entries of the resources deleted since could never be promoted
+DELETE FROM rsvp.waitlist w WHERE NOT EXISTS (SELECT 1 FROM rsvp.resources r WHERE r.id = w.resource_id);
+ALTER TABLE rsvp.waitlist
+    ADD CONSTRAINT waitlist_resource_id_fkey FOREIGN KEY (resource_id) REFERENCES rsvp.resources (id);
+
+-- waitlist entry the reservation is promoted from
+ALTER TABLE rsvp.reservations
+    ADD COLUMN waitlist_id BIGINT REFERENCES rsvp.waitlist (id) ON DELETE SET NULL;
+
+-- the promotions are made by the waitlist rather than whoever freed the window. An entry failing
+-- for any reason keeps waiting, without failing the change that freed the window
+CREATE OR REPLACE FUNCTION rsvp.promote_waitlist(rid text, during TSTZRANGE) RETURNS void AS $$
+DECLARE
+    _gap INTERVAL;
+    _entry rsvp.waitlist;
+    _id BIGINT;
+    _actor TEXT := current_setting('rsvp.actor', true);
+BEGIN
+    -- same lock as the capacity trigger, so the entries are promoted in order
+    PERFORM rsvp.lock_resource(rid);
+
+    -- retired resources can't be reserved
+    IF EXISTS (SELECT 1 FROM rsvp.resources WHERE id = rid AND retired) THEN
+        RETURN;
+    END IF;
+    SELECT buffer_before + buffer_after INTO _gap FROM rsvp.resources WHERE id = rid;
+    _gap := COALESCE(_gap, '0');
+
+    PERFORM set_config('rsvp.actor', 'waitlist', true);
+    FOR _entry IN
+        SELECT * FROM rsvp.waitlist
+        WHERE resource_id = rid AND reservation_id IS NULL
+            AND timespan && tstzrange(lower(during) - _gap, upper(during) + _gap, '[]')
+        ORDER BY id
+    LOOP
+        BEGIN
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, waitlist_id)
+                VALUES (_entry.user_id, _entry.resource_id, _entry.timespan, _entry.note, _entry.id)
+                RETURNING id INTO _id;
+        EXCEPTION WHEN others THEN
+            CONTINUE;
+        END;
+        UPDATE rsvp.waitlist SET reservation_id = _id, promoted_at = now() WHERE id = _entry.id;
+    END LOOP;
+    PERFORM set_config('rsvp.actor', COALESCE(_actor, ''), true);
+END;
+$$ LANGUAGE plpgsql;
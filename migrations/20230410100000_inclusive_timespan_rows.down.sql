@@ -0,0 +1,9 @@
+ALTER TABLE rsvp.reservations DISABLE TRIGGER USER;
+UPDATE rsvp.reservations SET timespan = tstzrange(lower(timespan), upper(timespan), '[)')
+    WHERE upper_inc(timespan);
+ALTER TABLE rsvp.reservations ENABLE TRIGGER USER;
+
+UPDATE rsvp.reservation_series SET timespan = tstzrange(lower(timespan), upper(timespan), '[)')
+    WHERE upper_inc(timespan);
+UPDATE rsvp.waitlist SET timespan = tstzrange(lower(timespan), upper(timespan), '[)')
+    WHERE upper_inc(timespan);
@@ -0,0 +1,12 @@
+-- the timespans made half-open by 20230215100000_half_open_timespan include their end again,
+-- like the ones written since. It's only a change of representation, so the triggers don't
+-- record it
+ALTER TABLE rsvp.reservations DISABLE TRIGGER USER;
+UPDATE rsvp.reservations SET timespan = tstzrange(lower(timespan), upper(timespan), '[]')
+    WHERE NOT upper_inc(timespan);
+ALTER TABLE rsvp.reservations ENABLE TRIGGER USER;
+
+UPDATE rsvp.reservation_series SET timespan = tstzrange(lower(timespan), upper(timespan), '[]')
+    WHERE NOT upper_inc(timespan);
+UPDATE rsvp.waitlist SET timespan = tstzrange(lower(timespan), upper(timespan), '[]')
+    WHERE NOT upper_inc(timespan);
@@ -0,0 +1,60 @@
+-- serialize the writes on a resource. The change lock is always taken first, since a
+-- transaction holding it might reserve other resources afterwards
+CREATE OR REPLACE FUNCTION rsvp.lock_resource(rid text) RETURNS void AS $$
+    SELECT pg_advisory_xact_lock(hashtext('rsvp.reservation_changes'));
+    SELECT pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(rid));
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _actor VARCHAR(64) := NULLIF(current_setting('rsvp.actor', true), '');
+BEGIN
+    -- the change ids are taken one transaction at a time, so they're visible in the order
+    -- they're taken and a reader going by id never skips a change committed late
+    PERFORM pg_advisory_xact_lock(hashtext('rsvp.reservation_changes'));
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (NEW.id, 'create', _actor, rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if any field changed, update reservation_changes
+        IF OLD IS DISTINCT FROM NEW THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+            INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+                VALUES (NEW.id, 'update', _actor, rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (OLD.id, 'delete', _actor, rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- the changes recorded since are numbered by the sequence again, in the same order
+CREATE TEMPORARY TABLE change_ids ON COMMIT DROP AS
+    SELECT id AS old_id,
+        (SELECT last_value FROM rsvp.reservation_changes_id_seq) + row_number() OVER (ORDER BY id) AS new_id
+    FROM rsvp.reservation_changes
+    WHERE id > (SELECT last_value FROM rsvp.reservation_changes_id_seq);
+UPDATE rsvp.consumer_groups g SET acked_id = COALESCE(
+        (SELECT max(new_id) FROM change_ids WHERE old_id <= g.acked_id),
+        (SELECT last_value FROM rsvp.reservation_changes_id_seq))
+    WHERE acked_id > (SELECT last_value FROM rsvp.reservation_changes_id_seq);
+UPDATE rsvp.webhook_dead_letters d SET change_id = c.new_id FROM change_ids c WHERE d.change_id = c.old_id;
+UPDATE rsvp.reservation_changes r SET id = c.new_id FROM change_ids c WHERE r.id = c.old_id;
+SELECT setval('rsvp.reservation_changes_id_seq', max(new_id)) FROM change_ids HAVING count(*) > 0;
+
+ALTER TABLE rsvp.reservation_changes
+    ALTER COLUMN id SET DEFAULT nextval('rsvp.reservation_changes_id_seq'),
+    ALTER COLUMN id TYPE integer;
+DROP FUNCTION rsvp.unsettled_change_id();
+DROP FUNCTION rsvp.next_change_id();
@@ -0,0 +1,58 @@
+-- a change id starts with the id of the transaction recording it, so once no transaction
+-- older than a reader's snapshot is in progress, no change could be committed before the ids
+-- it reads. The writers no longer take the change ids one transaction at a time
+CREATE OR REPLACE FUNCTION rsvp.next_change_id() RETURNS bigint AS $$
+DECLARE
+    _seq INTEGER := COALESCE(NULLIF(current_setting('rsvp.change_seq', true), ''), '0')::integer;
+BEGIN
+    IF _seq >= 1 << 20 THEN
+        RAISE EXCEPTION 'too many reservation changes in a transaction';
+    END IF;
+    PERFORM set_config('rsvp.change_seq', (_seq + 1)::text, true);
+    RETURN (pg_current_xact_id()::text::bigint << 20) + _seq;
+END;
+$$ LANGUAGE plpgsql;
+
+-- the first change id which might still be committed by a transaction in progress
+CREATE OR REPLACE FUNCTION rsvp.unsettled_change_id() RETURNS bigint AS $$
+    SELECT pg_snapshot_xmin(pg_current_snapshot())::text::bigint << 20;
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE rsvp.reservation_changes ALTER COLUMN id TYPE bigint,
+    ALTER COLUMN id SET DEFAULT rsvp.next_change_id();
+
+-- serialize the writes on a resource
+CREATE OR REPLACE FUNCTION rsvp.lock_resource(rid text) RETURNS void AS $$
+    SELECT pg_advisory_xact_lock(hashtext('rsvp.reservations'), hashtext(rid));
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _actor VARCHAR(64) := NULLIF(current_setting('rsvp.actor', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, new)
+            VALUES (NEW.id, 'create', rsvp.reservation_snapshot(NEW));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (NEW.id, 'create', _actor, rsvp.reservation_snapshot(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if any field changed, update reservation_changes
+        IF OLD IS DISTINCT FROM NEW THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, old, new)
+                VALUES (NEW.id, 'update', rsvp.reservation_snapshot(OLD), rsvp.reservation_snapshot(NEW));
+            INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+                VALUES (NEW.id, 'update', _actor, rsvp.reservation_snapshot(NEW));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, old)
+            VALUES (OLD.id, 'delete', rsvp.reservation_snapshot(OLD));
+        INSERT INTO rsvp.reservation_history (reservation_id, op, actor, snapshot)
+            VALUES (OLD.id, 'delete', _actor, rsvp.reservation_snapshot(OLD));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,117 @@
+use abi::{AvailabilityRequest, Resource, TimeSlot, Validator};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::types::PgRange;
+
+use crate::ReservationManager;
+
+impl ReservationManager {
+    /// free slots of the resource within the search window, long enough for the duration
+    pub async fn availability(
+        &self,
+        request: AvailabilityRequest,
+    ) -> Result<Vec<TimeSlot>, abi::Error> {
+        request.validate()?;
+
+        let resource = self.get_resource(&request.resource_id).await?;
+        if resource.retired {
+            return Err(abi::Error::RetiredResource(resource.id));
+        }
+
+        let reserved = self
+            .reserved_windows(&resource, request.get_timespan())
+            .await?;
+        Ok(request.free_slots(&reserved, resource.capacity as usize))
+    }
+
+    /// windows of the active reservations on the resource overlapping the timespan. They are
+    /// widened by the buffers of both sides, so a free window leaves room for its own buffers
+    /// as well. A block is repeated to take all the seats
+    pub(crate) async fn reserved_windows(
+        &self,
+        resource: &Resource,
+        timespan: PgRange<DateTime<Utc>>,
+    ) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>, abi::Error> {
+        let (before, after) = resource.buffers();
+        let gap = before + after;
+        let reserved: Vec<(DateTime<Utc>, DateTime<Utc>, bool)> = sqlx::query_as(
+            "SELECT lower(timespan), upper(timespan), status = 'blocked' FROM rsvp.reservations WHERE resource_id = $1 AND status <> 'cancelled' AND timespan && tstzrange(lower($2::tstzrange) - $3, upper($2::tstzrange) + $3, '[]')",
+        )
+        .bind(&resource.id)
+        .bind(timespan)
+        .bind(gap)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(reserved
+            .into_iter()
+            .flat_map(|(start, end, blocked)| {
+                let seats = if blocked { resource.capacity } else { 1 };
+                std::iter::repeat_n((start - gap, end + gap), seats as usize)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::convert_to_utc_time;
+    use chrono::Duration;
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn availability_should_return_free_slots() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        for (start, end) in [("09:00", "10:00"), ("10:30", "11:30"), ("14:00", "15:00")] {
+            let rsvp = abi::Reservation::new_pending(
+                "alice",
+                "meeting-room-1",
+                format!("2023-01-25T{start}:00-0700").parse().unwrap(),
+                format!("2023-01-25T{end}:00-0700").parse().unwrap(),
+                "",
+            );
+            manager.reserve(rsvp).await.unwrap();
+        }
+
+        let t = |s: &str| -> DateTime<Utc> { format!("2023-01-25T{s}:00-0700").parse().unwrap() };
+        let request = AvailabilityRequest::new(
+            "meeting-room-1",
+            t("08:00"),
+            t("18:00"),
+            Duration::hours(1),
+            Some(Duration::hours(1)),
+        );
+        let slots: Vec<_> = manager
+            .availability(request)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| {
+                (
+                    convert_to_utc_time(s.start.as_ref().unwrap()),
+                    convert_to_utc_time(s.end.as_ref().unwrap()),
+                )
+            })
+            .collect();
+        // reservations take both their start and end, so the hours touching them are not free
+        assert_eq!(
+            slots,
+            vec![(t("12:00"), t("13:00")), (t("16:00"), t("18:00"))]
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn availability_of_unknown_resource_should_reject() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let request = AvailabilityRequest::new(
+            "no-such-room",
+            "2023-01-25T08:00:00-0700".parse().unwrap(),
+            "2023-01-25T18:00:00-0700".parse().unwrap(),
+            Duration::hours(1),
+            None,
+        );
+        let err = manager.availability(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("no-such-room".into()));
+    }
+}
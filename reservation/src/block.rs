@@ -0,0 +1,251 @@
+use abi::{
+    convert_to_utc_time, BlockRequest, BlockResponse, ListBlocksRequest, Reservation,
+    ReservationStatus, Resource, Validator,
+};
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    manager::insert_reservation, resource::check_resource, series::insert_series,
+    ReservationManager,
+};
+
+impl ReservationManager {
+    /// block the resource for maintenance. The overlapping user reservations are cancelled if
+    /// asked, otherwise the block fails on them
+    pub async fn block(&self, request: BlockRequest) -> Result<BlockResponse, abi::Error> {
+        request.validate()?;
+
+        let mut tx = self.begin().await?;
+        // takes the same lock as the capacity trigger, so nothing could be reserved after the
+        // cancellation
+        check_resource(&mut tx, &request.resource_id).await?;
+
+        let mut cancelled = vec![];
+        if request.cancel_conflicts {
+            let resource: Resource = sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+                .bind(&request.resource_id)
+                .fetch_optional(&mut tx)
+                .await?
+                .ok_or_else(|| abi::Error::UnknownResource(request.resource_id.clone()))?;
+            let windows = match request.series() {
+                Some(series) => series.expand()?,
+                None => vec![request.block()],
+            };
+            for window in windows {
+                let rsvps = cancel_overlapping(&mut tx, &resource, &window, &request).await?;
+                cancelled.extend(rsvps);
+            }
+        }
+
+        let (series_id, blocks) = match request.series() {
+            Some(series) => {
+                let (series, blocks) =
+                    insert_series(&mut tx, series, ReservationStatus::Blocked).await?;
+                (series.id, blocks)
+            }
+            None => (0, vec![insert_reservation(&mut tx, request.block()).await?]),
+        };
+        tx.commit().await?;
+        Ok(BlockResponse {
+            blocks,
+            series_id,
+            cancelled,
+        })
+    }
+
+    /// blocks overlapping the window, order by start time
+    pub async fn list_blocks(
+        &self,
+        request: ListBlocksRequest,
+    ) -> Result<Vec<Reservation>, abi::Error> {
+        request.validate()?;
+        let blocks = sqlx::query_as(
+            "SELECT * FROM rsvp.reservations WHERE status = 'blocked' AND ($1 = '' OR resource_id = $1) AND timespan && tstzrange($2::timestamptz, $3::timestamptz, '[]') ORDER BY lower(timespan), id",
+        )
+        .bind(&request.resource_id)
+        .bind(request.start.as_ref().map(convert_to_utc_time))
+        .bind(request.end.as_ref().map(convert_to_utc_time))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(blocks)
+    }
+
+    /// lift a block, the resource could be reserved in its window again
+    pub async fn lift_block(&self, id: i64) -> Result<Reservation, abi::Error> {
+        id.validate()?;
+        let mut tx = self.begin().await?;
+        let block = sqlx::query_as(
+            "DELETE FROM rsvp.reservations WHERE id = $1 AND status = 'blocked' RETURNING *",
+        )
+        .bind(id)
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(block)
+    }
+}
+
+/// cancel the user reservations whose buffered windows overlap the block's
+async fn cancel_overlapping(
+    tx: &mut Transaction<'_, Postgres>,
+    resource: &Resource,
+    block: &Reservation,
+    request: &BlockRequest,
+) -> Result<Vec<Reservation>, abi::Error> {
+    let (before, after) = resource.buffers();
+    let reason = if request.reason.is_empty() {
+        "resource blocked".to_string()
+    } else {
+        format!("resource blocked: {}", request.reason)
+    };
+    let rsvps = sqlx::query_as(
+        "UPDATE rsvp.reservations SET status = 'cancelled', cancel_reason = $3, cancelled_at = now(), cancelled_by = $4 WHERE resource_id = $1 AND status IN ('pending', 'confirmed') AND timespan && tstzrange(lower($2::tstzrange) - $5, upper($2::tstzrange) + $5, '[]') RETURNING *",
+    )
+    .bind(&resource.id)
+    .bind(block.get_timespan())
+    .bind(reason)
+    .bind(&request.blocked_by)
+    .bind(before + after)
+    .fetch_all(&mut *tx)
+    .await?;
+    Ok(rsvps)
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::{AvailabilityRequest, ReservationConflictInfo};
+    use chrono::{DateTime, Duration, Utc};
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn block_should_take_all_seats_until_lifted() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut lab = Resource::new("lab-1", "Lab", "lab");
+        lab.capacity = 2;
+        manager.create_resource(lab).await.unwrap();
+        let alice = manager
+            .reserve(make_reservation("alice", "lab-1", "09:00", "10:00"))
+            .await
+            .unwrap();
+
+        // the block fails on the user reservation unless it's cancelled
+        let request = make_block("lab-1", "08:00", "12:00");
+        let err = manager.block(request.clone()).await.unwrap_err();
+        let conflict = match err {
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict)) => conflict,
+            e => panic!("unexpected error: {e:?}"),
+        };
+        assert_eq!(conflict.taken, vec![alice.id]);
+
+        let ret = manager.block(request.cancelling_conflicts()).await.unwrap();
+        assert_eq!(ret.series_id, 0);
+        assert_eq!(ret.cancelled.len(), 1);
+        assert_eq!(ret.cancelled[0].id, alice.id);
+        assert_eq!(
+            ret.cancelled[0].cancel_reason,
+            "resource blocked: calibration"
+        );
+        assert_eq!(ret.cancelled[0].cancelled_by, "admin");
+        let block = ret.blocks[0].clone();
+        assert_eq!(block.status(), ReservationStatus::Blocked);
+
+        // the other seat is blocked as well
+        let bob = make_reservation("bob", "lab-1", "11:00", "11:30");
+        let err = manager.reserve(bob.clone()).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        let request =
+            AvailabilityRequest::new("lab-1", t("06:00"), t("18:00"), Duration::minutes(30), None);
+        let slots = manager.availability(request).await.unwrap();
+        assert_eq!(slots.len(), 2);
+        let us = Duration::microseconds(1);
+        let block_start = convert_to_utc_time(block.start.as_ref().unwrap());
+        let block_end = convert_to_utc_time(block.end.as_ref().unwrap());
+        assert_eq!(
+            slots[0].end,
+            Some(abi::convert_to_timestamp(&(block_start - us)))
+        );
+        assert_eq!(
+            slots[1].start,
+            Some(abi::convert_to_timestamp(&(block_end + us)))
+        );
+
+        let blocks = manager
+            .list_blocks(ListBlocksRequest::new("lab-1"))
+            .await
+            .unwrap();
+        assert_eq!(blocks, vec![block.clone()]);
+
+        assert_eq!(manager.lift_block(block.id).await.unwrap(), block);
+        manager.reserve(bob).await.unwrap();
+        let err = manager.lift_block(block.id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_not_make_blocks() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rsvp = make_reservation("alice", "meeting-room-1", "09:00", "10:00");
+        rsvp.status = ReservationStatus::Blocked as i32;
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(ReservationStatus::Blocked as i32)
+        );
+        let err = manager.reserve_many(vec![rsvp]).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(ReservationStatus::Blocked as i32)
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn recurring_block_should_cancel_conflicts_of_every_occurrence() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rsvp = make_reservation("alice", "meeting-room-1", "09:30", "10:30");
+        let next_week = |ts: &prost_types::Timestamp| {
+            abi::convert_to_timestamp(&(convert_to_utc_time(ts) + Duration::weeks(1)))
+        };
+        rsvp.start = rsvp.start.as_ref().map(next_week);
+        rsvp.end = rsvp.end.as_ref().map(next_week);
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        // not overlapping any occurrence
+        manager
+            .reserve(make_reservation("bob", "meeting-room-1", "13:00", "14:00"))
+            .await
+            .unwrap();
+
+        let request = make_block("meeting-room-1", "09:00", "12:00")
+            .with_rrule("FREQ=WEEKLY;COUNT=3", "America/Phoenix")
+            .cancelling_conflicts();
+        let ret = manager.block(request).await.unwrap();
+        assert_ne!(ret.series_id, 0);
+        assert_eq!(ret.blocks.len(), 3);
+        assert!(ret
+            .blocks
+            .iter()
+            .all(|b| b.status() == ReservationStatus::Blocked && b.series_id == ret.series_id));
+        assert_eq!(ret.cancelled.len(), 1);
+        assert_eq!(ret.cancelled[0].id, rsvp.id);
+
+        let blocks = manager
+            .list_blocks(ListBlocksRequest::new(""))
+            .await
+            .unwrap();
+        assert_eq!(blocks, ret.blocks);
+    }
+
+    fn t(s: &str) -> DateTime<Utc> {
+        format!("2023-01-25T{s}:00-0700").parse().unwrap()
+    }
+
+    fn make_block(rid: &str, start: &str, end: &str) -> BlockRequest {
+        BlockRequest::new(rid, t(start).into(), t(end).into(), "calibration", "admin")
+    }
+
+    fn make_reservation(uid: &str, rid: &str, start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(uid, rid, t(start).into(), t(end).into(), "")
+    }
+}
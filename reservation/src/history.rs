@@ -0,0 +1,96 @@
+use abi::{HistoryEntry, ReservationId, Validator};
+
+use crate::ReservationManager;
+
+impl ReservationManager {
+    /// who changed the reservation and when, the oldest first. Deleted reservations keep
+    /// their history
+    pub async fn history(&self, id: ReservationId) -> Result<Vec<HistoryEntry>, abi::Error> {
+        id.validate()?;
+        let entries: Vec<HistoryEntry> = sqlx::query_as(
+            "SELECT * FROM rsvp.reservation_history WHERE reservation_id = $1 ORDER BY id",
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+        if entries.is_empty() {
+            return Err(abi::Error::NotFound);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::{CancelRequest, Reservation, ReservationStatus, ReservationUpdateType};
+
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn history_should_record_actors_and_keep_deleted_reservations() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let rsvp = Reservation::new_pending(
+            "alice",
+            "ocean-view-room-417",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "",
+        );
+        let rsvp = manager.acting_as("alice").reserve(rsvp).await.unwrap();
+        manager
+            .acting_as("front-desk")
+            .change_status(rsvp.id)
+            .await
+            .unwrap();
+        manager
+            .cancel(CancelRequest::new(rsvp.id).with_reason("no show", "night-shift"))
+            .await
+            .unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        let history = manager.history(rsvp.id).await.unwrap();
+        let timeline: Vec<_> = history
+            .iter()
+            .map(|entry| {
+                let rsvp = entry.reservation.as_ref().unwrap();
+                (entry.op(), entry.actor.as_str(), rsvp.status())
+            })
+            .collect();
+        assert_eq!(
+            timeline,
+            vec![
+                (
+                    ReservationUpdateType::Create,
+                    "alice",
+                    ReservationStatus::Pending
+                ),
+                (
+                    ReservationUpdateType::Update,
+                    "front-desk",
+                    ReservationStatus::Confirmed
+                ),
+                (
+                    ReservationUpdateType::Update,
+                    "night-shift",
+                    ReservationStatus::Cancelled
+                ),
+                (
+                    ReservationUpdateType::Delete,
+                    "",
+                    ReservationStatus::Cancelled
+                ),
+            ]
+        );
+        assert!(history.windows(2).all(|w| w[0].id < w[1].id));
+
+        // the history can't be rewritten
+        let ret = sqlx::query("DELETE FROM rsvp.reservation_history")
+            .execute(&migrated_pool)
+            .await;
+        assert!(ret.is_err());
+
+        let err = manager.history(rsvp.id + 1).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+}
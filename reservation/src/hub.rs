@@ -0,0 +1,194 @@
+use std::{sync::Arc, time::Duration};
+
+use abi::ReservationChange;
+use sqlx::postgres::PgListener;
+use sqlx::{PgPool, Row};
+use tokio::sync::{broadcast, OnceCell};
+use tokio::time;
+use tracing::log::warn;
+
+/// channel used by `rsvp.reservations_trigger()` to notify reservation changes
+const CHANGES_CHANNEL: &str = "reservation_update";
+
+/// delay before the hub retries after a database error
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// delay before the hub fetches again while a change waits for older transactions, since a
+/// transaction rolled back notifies nobody
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// shares a single database LISTEN connection among all the change listeners
+#[derive(Debug)]
+pub(crate) struct ChangeHub {
+    pool: PgPool,
+    capacity: usize,
+    sender: OnceCell<broadcast::Sender<Arc<ReservationChange>>>,
+}
+
+impl ChangeHub {
+    pub fn new(pool: PgPool, capacity: usize) -> Self {
+        Self {
+            pool,
+            capacity,
+            sender: OnceCell::new(),
+        }
+    }
+
+    /// receive the changes recorded after this call, the hub is started by the first subscriber.
+    /// Subscribe before reading a cursor or a snapshot, so no change could slip in between
+    pub async fn subscribe(
+        &self,
+    ) -> Result<broadcast::Receiver<Arc<ReservationChange>>, abi::Error> {
+        let sender = self
+            .sender
+            .get_or_try_init(|| async {
+                let (sender, _) = broadcast::channel(self.capacity);
+                let mut listener = PgListener::connect_with(&self.pool).await?;
+                listener.listen(CHANGES_CHANNEL).await?;
+                let cursor = latest_change_id(&self.pool).await?;
+
+                tokio::spawn(broadcast_changes(
+                    self.pool.clone(),
+                    listener,
+                    cursor,
+                    sender.clone(),
+                ));
+                Ok::<_, abi::Error>(sender)
+            })
+            .await?;
+        Ok(sender.subscribe())
+    }
+}
+
+/// fetch the changes once for every notification and broadcast them to the subscribers
+async fn broadcast_changes(
+    pool: PgPool,
+    mut listener: PgListener,
+    mut cursor: i64,
+    sender: broadcast::Sender<Arc<ReservationChange>>,
+) {
+    let mut unsettled = false;
+    loop {
+        // PgListener reconnects by itself, the missed changes are fetched by cursor
+        if unsettled {
+            let _ = time::timeout(SETTLE_DELAY, listener.recv()).await;
+        } else if let Err(e) = listener.recv().await {
+            warn!("Change hub listen error: {e:?}");
+            time::sleep(RETRY_DELAY).await;
+        }
+
+        let changes = match fetch_changes(&pool, cursor).await {
+            Ok(changes) => changes,
+            Err(e) => {
+                warn!("Change hub fetch error: {e:?}");
+                time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        for change in changes {
+            cursor = change.id;
+            // no subscriber at the moment is fine
+            let _ = sender.send(Arc::new(change));
+        }
+        unsettled = match has_unsettled_changes(&pool, cursor).await {
+            Ok(unsettled) => unsettled,
+            Err(e) => {
+                warn!("Change hub fetch error: {e:?}");
+                true
+            }
+        };
+    }
+}
+
+/// id of the latest settled change, 0 if there's no change
+pub(crate) async fn latest_change_id(pool: &PgPool) -> Result<i64, abi::Error> {
+    let id = sqlx::query(
+        "SELECT COALESCE(MAX(id), 0)::bigint FROM rsvp.reservation_changes WHERE id < rsvp.unsettled_change_id()",
+    )
+    .fetch_one(pool)
+    .await?
+    .get(0);
+    Ok(id)
+}
+
+/// whether a change after the cursor is recorded but not settled yet
+async fn has_unsettled_changes(pool: &PgPool, cursor: i64) -> Result<bool, abi::Error> {
+    let unsettled = sqlx::query(
+        "SELECT EXISTS (SELECT 1 FROM rsvp.reservation_changes WHERE id > $1 AND id >= rsvp.unsettled_change_id())",
+    )
+    .bind(cursor)
+    .fetch_one(pool)
+    .await?
+    .get(0);
+    Ok(unsettled)
+}
+
+/// fetch the settled changes recorded after the given change id, in order. A change is held
+/// back while an older transaction is in progress, since it could still commit a lower id
+pub(crate) async fn fetch_changes(
+    pool: &PgPool,
+    cursor: i64,
+) -> Result<Vec<ReservationChange>, abi::Error> {
+    let changes = sqlx::query_as(
+        "SELECT id::bigint, reservation_id, op, old, new, create_at FROM rsvp.reservation_changes WHERE id > $1 AND id < rsvp.unsettled_change_id() ORDER BY id",
+    )
+    .bind(cursor)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::Reservation;
+
+    use super::*;
+    use crate::manager::insert_reservation;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn changes_should_be_visible_in_id_order() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut tx = manager.begin().await.unwrap();
+        let alice = insert_reservation(&mut tx, make_reservation("alice", "ixia-test-1"))
+            .await
+            .unwrap();
+
+        // the writer on the other resource isn't blocked, but its later change id is held back
+        let bob = time::timeout(
+            Duration::from_secs(5),
+            manager.reserve(make_reservation("bob", "ixia-test-2")),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(fetch_changes(&migrated_pool, 0).await.unwrap().is_empty());
+        assert_eq!(latest_change_id(&migrated_pool).await.unwrap(), 0);
+
+        tx.commit().await.unwrap();
+        let ids = time::timeout(Duration::from_secs(5), async {
+            loop {
+                let changes = fetch_changes(&migrated_pool, 0).await.unwrap();
+                if changes.len() == 2 {
+                    break changes.iter().map(|c| c.reservation_id).collect::<Vec<_>>();
+                }
+                time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(ids, vec![alice.id, bob.id]);
+    }
+
+    fn make_reservation(uid: &str, rid: &str) -> Reservation {
+        Reservation::new_pending(
+            uid,
+            rid,
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-01-25T16:00:00-0700".parse().unwrap(),
+            "",
+        )
+    }
+}
@@ -1,19 +1,50 @@
+mod availability;
+mod block;
+mod history;
+mod hub;
+mod listener;
+mod live_query;
 mod manager;
+mod resource;
+mod retention;
+mod search;
+mod series;
+mod sink;
+mod suggest;
+#[cfg(test)]
+mod test_utils;
+mod waitlist;
+mod webhook;
+
+use std::{sync::Arc, time::Duration};
 
 use abi::ReservationId;
 use async_trait::async_trait;
+use hub::ChangeHub;
 use sqlx::PgPool;
 use tokio::sync::mpsc;
 
-#[derive(Debug)]
+pub use sink::{sink_from_config, EventSink, FileSink, HttpSink, StdoutSink};
+pub use webhook::{sign, WebhookDispatcher, CHANGE_ID_HEADER, SIGNATURE_HEADER};
+
+#[derive(Debug, Clone)]
 pub struct ReservationManager {
     pool: PgPool,
+    ack_timeout: Duration,
+    hub: Arc<ChangeHub>,
+    /// who makes the changes, recorded in the reservation history
+    actor: Option<String>,
 }
 
 #[async_trait]
 pub trait Rsvp {
     /// make a reservation
     async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error>;
+    /// make all the reservations in one transaction, none is made if any conflicts
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
     /// change reservation status (if current status is pending, change it to confirmed)
     async fn change_status(&self, rsvp: ReservationId) -> Result<abi::Reservation, abi::Error>;
     /// update note
@@ -22,8 +53,19 @@ pub trait Rsvp {
         id: ReservationId,
         note: String,
     ) -> Result<abi::Reservation, abi::Error>;
+    /// update the reservation fields listed in the update mask
+    async fn update(&self, request: abi::UpdateRequest) -> Result<abi::Reservation, abi::Error>;
+    /// move reservation to another time window
+    async fn reschedule(
+        &self,
+        request: abi::RescheduleRequest,
+    ) -> Result<abi::Reservation, abi::Error>;
     /// delete reservation
     async fn delete(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
+    /// cancel reservation, it's kept with the reason but takes no seat anymore
+    async fn cancel(&self, request: abi::CancelRequest) -> Result<abi::Reservation, abi::Error>;
+    /// restore a cancelled reservation with its former status if its window is still free
+    async fn restore(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
     /// get reservation by id
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
     /// query reservations
@@ -36,4 +78,16 @@ pub trait Rsvp {
         &self,
         filter: abi::ReservationFilter,
     ) -> Result<(abi::FilterPager, Vec<abi::Reservation>), abi::Error>;
+    /// listen to reservation changes (create/update/delete), replay from offset if given
+    async fn listen(
+        &self,
+        request: abi::ListenRequest,
+    ) -> Result<mpsc::Receiver<Result<abi::ListenResponse, abi::Error>>, abi::Error>;
+    /// acknowledge the changes up to id for a consumer group, return the acknowledged offset
+    async fn ack(&self, group: String, id: i64) -> Result<i64, abi::Error>;
+    /// query reservations, then keep sending the deltas of the result set
+    async fn live_query(
+        &self,
+        query: abi::ReservationQuery,
+    ) -> Result<mpsc::Receiver<Result<abi::LiveQueryEvent, abi::Error>>, abi::Error>;
 }
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use abi::ReservationChange;
+use sqlx::{PgPool, Row};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{self, Instant};
+use tracing::log::warn;
+
+use crate::hub::{fetch_changes, latest_change_id, ChangeHub};
+
+/// streams the changes recorded in `rsvp.reservation_changes` after a cursor
+pub(crate) struct ChangeListener {
+    pool: PgPool,
+    changes: broadcast::Receiver<Arc<ReservationChange>>,
+    cursor: i64,
+    group: Option<ConsumerGroup>,
+    filter: abi::ListenRequest,
+}
+
+/// delivery state of a consumer group, used to redeliver unacknowledged changes
+struct ConsumerGroup {
+    name: String,
+    acked: i64,
+    ack_timeout: Duration,
+    deadline: Option<Instant>,
+}
+
+/// what the listener should do next
+enum Flow {
+    /// keep forwarding the live changes
+    Live,
+    /// replay the recorded changes after cursor first
+    Replay,
+    /// the receiver is dropped
+    Closed,
+}
+
+impl ChangeListener {
+    pub async fn new(
+        hub: &ChangeHub,
+        pool: PgPool,
+        request: abi::ListenRequest,
+        ack_timeout: Duration,
+    ) -> Result<Self, abi::Error> {
+        let changes = hub.subscribe().await?;
+
+        let group = if request.group.is_empty() {
+            None
+        } else {
+            let acked: i64 = sqlx::query(
+                "INSERT INTO rsvp.consumer_groups (name) VALUES ($1) ON CONFLICT (name) DO UPDATE SET update_at = now() RETURNING acked_id",
+            )
+            .bind(&request.group)
+            .fetch_one(&pool)
+            .await?
+            .get(0);
+            Some(ConsumerGroup {
+                name: request.group.clone(),
+                acked,
+                ack_timeout,
+                deadline: None,
+            })
+        };
+
+        // without offset, only the changes after now will be sent
+        let cursor = match (&group, request.offset) {
+            (Some(group), _) => group.acked,
+            (None, Some(offset)) => offset,
+            (None, None) => latest_change_id(&pool).await?,
+        };
+
+        Ok(Self {
+            pool,
+            changes,
+            cursor,
+            group,
+            filter: request,
+        })
+    }
+
+    /// send the changes to tx until the receiver is dropped or an error occurs
+    pub async fn run<T>(mut self, tx: mpsc::Sender<Result<T, abi::Error>>)
+    where
+        T: From<ReservationChange>,
+    {
+        let mut flow = Flow::Replay;
+        loop {
+            let ret = match flow {
+                Flow::Replay => self.replay(&tx).await,
+                Flow::Live => {
+                    let deadline = self.group.as_ref().and_then(|g| g.deadline);
+                    tokio::select! {
+                        change = self.changes.recv() => self.forward(change, &tx).await,
+                        _ = wait_until(deadline) => self.check_acked().await,
+                        _ = tx.closed() => Ok(Flow::Closed),
+                    }
+                }
+                // rx is dropped, so client disconnected
+                Flow::Closed => break,
+            };
+            flow = match ret {
+                Ok(flow) => flow,
+                Err(e) => {
+                    warn!("Listen error: {e:?}");
+                    let _ = tx.send(Err(e)).await;
+                    break;
+                }
+            };
+        }
+    }
+
+    /// send all the recorded changes after cursor
+    async fn replay<T>(
+        &mut self,
+        tx: &mpsc::Sender<Result<T, abi::Error>>,
+    ) -> Result<Flow, abi::Error>
+    where
+        T: From<ReservationChange>,
+    {
+        for change in fetch_changes(&self.pool, self.cursor).await? {
+            if !self.deliver(change, tx).await {
+                return Ok(Flow::Closed);
+            }
+        }
+        Ok(Flow::Live)
+    }
+
+    /// send a live change from the hub, the listener is dropped if it falls behind the buffer
+    async fn forward<T>(
+        &mut self,
+        change: Result<Arc<ReservationChange>, broadcast::error::RecvError>,
+        tx: &mpsc::Sender<Result<T, abi::Error>>,
+    ) -> Result<Flow, abi::Error>
+    where
+        T: From<ReservationChange>,
+    {
+        let change = match change {
+            Ok(change) => change,
+            Err(broadcast::error::RecvError::Lagged(_)) => {
+                return Err(abi::Error::ListenLagged(self.cursor))
+            }
+            Err(broadcast::error::RecvError::Closed) => return Err(abi::Error::Unknown),
+        };
+        // already replayed
+        if change.id <= self.cursor {
+            return Ok(Flow::Live);
+        }
+        if self.deliver(change.as_ref().clone(), tx).await {
+            Ok(Flow::Live)
+        } else {
+            Ok(Flow::Closed)
+        }
+    }
+
+    /// move the cursor to the change and send it if matched, return false if the receiver is dropped
+    async fn deliver<T>(
+        &mut self,
+        change: ReservationChange,
+        tx: &mpsc::Sender<Result<T, abi::Error>>,
+    ) -> bool
+    where
+        T: From<ReservationChange>,
+    {
+        self.cursor = change.id;
+        if !self.filter.matches(&change) {
+            return true;
+        }
+        if tx.send(Ok(change.into())).await.is_err() {
+            return false;
+        }
+
+        if let Some(group) = self.group.as_mut() {
+            if group.deadline.is_none() {
+                group.deadline = Some(Instant::now() + group.ack_timeout);
+            }
+        }
+        true
+    }
+
+    /// rewind the cursor to the acknowledged offset if the group stops acknowledging in time
+    async fn check_acked(&mut self) -> Result<Flow, abi::Error> {
+        let group = match self.group.as_mut() {
+            Some(group) => group,
+            None => return Ok(Flow::Live),
+        };
+
+        let acked: i64 = sqlx::query("SELECT acked_id FROM rsvp.consumer_groups WHERE name = $1")
+            .bind(&group.name)
+            .fetch_one(&self.pool)
+            .await?
+            .get(0);
+
+        let flow = if acked >= self.cursor {
+            // everything delivered has been acknowledged
+            group.deadline = None;
+            Flow::Live
+        } else if acked > group.acked {
+            // the group is making progress, give it more time
+            group.deadline = Some(Instant::now() + group.ack_timeout);
+            Flow::Live
+        } else {
+            // redeliver the changes after the acknowledged offset
+            self.cursor = acked;
+            group.deadline = None;
+            Flow::Replay
+        };
+        group.acked = acked;
+        Ok(flow)
+    }
+}
+
+async fn wait_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => futures::future::pending().await,
+    }
+}
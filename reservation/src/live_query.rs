@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use abi::{LiveQueryEvent, LiveQueryEventType, ReservationChange, ReservationQuery};
+use sqlx::{PgPool, Row};
+use tokio::sync::mpsc;
+
+use crate::{manager::string_to_option, ReservationId};
+
+/// result set of a live query, turns the reservation changes into deltas of it
+pub(crate) struct LiveQuery {
+    query: ReservationQuery,
+    /// ids of the reservations in the result set
+    members: HashSet<ReservationId>,
+    /// id of the last change applied to the result set
+    offset: i64,
+}
+
+impl LiveQuery {
+    /// query the reservations and the latest change id from the same database snapshot
+    pub async fn snapshot(
+        pool: &PgPool,
+        query: ReservationQuery,
+    ) -> Result<(Self, Vec<abi::Reservation>), abi::Error> {
+        let user_id = string_to_option(&query.user_id);
+        let resource_id = string_to_option(&query.resource_id);
+        let start = query.start.as_ref().map(abi::convert_to_utc_time);
+        let end = query.end.as_ref().map(abi::convert_to_utc_time);
+        let status = abi::ReservationStatus::from_i32(query.status)
+            .unwrap_or(abi::ReservationStatus::Pending);
+
+        let mut tx = pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut tx)
+            .await?;
+        // the changes after the settled ones are replayed, even those already in the snapshot,
+        // which leaves their reservations in the same state
+        let offset: i64 = sqlx::query(
+            "SELECT COALESCE(MAX(id), 0)::bigint FROM rsvp.reservation_changes WHERE id < rsvp.unsettled_change_id()",
+        )
+        .fetch_one(&mut tx)
+        .await?
+        .get(0);
+        let rsvps: Vec<abi::Reservation> =
+            sqlx::query_as("SELECT * FROM rsvp.query($1,$2,$3,$4,$5::rsvp.reservation_status,$6)")
+                .bind(user_id)
+                .bind(resource_id)
+                .bind(start)
+                .bind(end)
+                .bind(status.to_string())
+                .bind(query.desc)
+                .fetch_all(&mut tx)
+                .await?;
+        tx.commit().await?;
+
+        let live_query = Self {
+            query,
+            members: rsvps.iter().map(|r| r.id).collect(),
+            offset,
+        };
+        Ok((live_query, rsvps))
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// send the snapshot, then the deltas of the changes until the receiver is dropped
+    pub async fn run(
+        mut self,
+        snapshot: Vec<abi::Reservation>,
+        mut changes: mpsc::Receiver<Result<ReservationChange, abi::Error>>,
+        tx: mpsc::Sender<Result<LiveQueryEvent, abi::Error>>,
+    ) {
+        let events = snapshot
+            .into_iter()
+            .map(|r| LiveQueryEvent::new(LiveQueryEventType::Snapshot, Some(r), self.offset))
+            .chain(std::iter::once(LiveQueryEvent::new(
+                LiveQueryEventType::Synced,
+                None,
+                self.offset,
+            )));
+        for event in events {
+            if tx.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+
+        while let Some(change) = changes.recv().await {
+            let event = match change {
+                Ok(change) => match self.apply(change) {
+                    Some(event) => Ok(event),
+                    None => continue,
+                },
+                Err(e) => Err(e),
+            };
+            // rx is dropped, so client disconnected
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// apply the change to the result set, return the delta if the result set is affected
+    fn apply(&mut self, change: ReservationChange) -> Option<LiveQueryEvent> {
+        self.offset = change.id;
+        let id = change.reservation_id;
+        let new = change
+            .new
+            .map(abi::Reservation::from)
+            .filter(|r| self.query.matches(r));
+
+        let (event_type, reservation) = match (self.members.contains(&id), new) {
+            (false, Some(rsvp)) => {
+                self.members.insert(id);
+                (LiveQueryEventType::Insert, rsvp)
+            }
+            (true, Some(rsvp)) => (LiveQueryEventType::Update, rsvp),
+            (true, None) => {
+                self.members.remove(&id);
+                (LiveQueryEventType::Remove, change.old?.into())
+            }
+            (false, None) => return None,
+        };
+        Some(LiveQueryEvent::new(
+            event_type,
+            Some(reservation),
+            self.offset,
+        ))
+    }
+}
@@ -5,42 +5,46 @@ use abi::ToSql;
 use abi::Validator;
 use async_trait::async_trait;
 use futures::StreamExt;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
 use sqlx::postgres::PgPoolOptions;
 use sqlx::Either;
-use sqlx::{PgPool, Row};
+use sqlx::{Acquire, PgConnection, PgPool, Postgres, Row, Transaction};
 use tokio::sync::mpsc;
 use tracing::info;
 use tracing::log::warn;
 
+use crate::hub::ChangeHub;
+use crate::listener::ChangeListener;
+use crate::live_query::LiveQuery;
+use crate::resource::check_resource;
 use crate::ReservationManager;
 use crate::Rsvp;
 
 #[async_trait]
 impl Rsvp for ReservationManager {
-    async fn reserve(&self, mut rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error> {
-        rsvp.validate()?;
-
-        let status = abi::ReservationStatus::from_i32(rsvp.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
-
-        let timespan = rsvp.get_timespan();
-
-        // generate a insert sql for the reservation
-        // execute the sql
-        let id = sqlx::query(
-            "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status) VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status) RETURNING id"
-        )
-        .bind(rsvp.user_id.clone())
-        .bind(rsvp.resource_id.clone())
-        .bind(timespan)
-        .bind(rsvp.note.clone())
-        .bind(status.to_string())
-        .fetch_one(&self.pool)
-        .await?.get(0);
+    async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error> {
+        rsvp.validate_new()?;
+        let mut tx = self.begin().await?;
+        let rsvp = insert_reservation(&mut tx, rsvp).await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
 
-        rsvp.id = id;
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        for rsvp in &rsvps {
+            rsvp.validate_new()?;
+        }
 
-        Ok(rsvp)
+        let mut tx = self.begin().await?;
+        let reserved = insert_all(&mut tx, rsvps).await?;
+        tx.commit().await?;
+        Ok(reserved)
     }
 
     async fn change_status(
@@ -51,7 +55,9 @@ impl Rsvp for ReservationManager {
         if id == 0 {
             return Err(abi::Error::InvalidReservationId(id));
         }
-        let rsvp: abi::Reservation = sqlx::query_as("UPDATE rsvp.reservations  SET status = 'confirmed' WHERE id = $1 AND status = 'pending' RETURNING *").bind(id).fetch_one(&self.pool).await?;
+        let mut tx = self.begin().await?;
+        let rsvp: abi::Reservation = sqlx::query_as("UPDATE rsvp.reservations  SET status = 'confirmed' WHERE id = $1 AND status = 'pending' RETURNING *").bind(id).fetch_one(&mut tx).await?;
+        tx.commit().await?;
         Ok(rsvp)
     }
 
@@ -61,12 +67,59 @@ impl Rsvp for ReservationManager {
         note: String,
     ) -> Result<abi::Reservation, abi::Error> {
         id.validate()?;
+        let mut tx = self.begin().await?;
         let rsvp: abi::Reservation =
             sqlx::query_as("UPDATE rsvp.reservations SET note = $1 WHERE id = $2 RETURNING *")
                 .bind(note)
                 .bind(id)
-                .fetch_one(&self.pool)
+                .fetch_one(&mut tx)
+                .await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
+    async fn update(&self, request: abi::UpdateRequest) -> Result<abi::Reservation, abi::Error> {
+        request.validate()?;
+
+        let mut tx = self.begin().await?;
+        let mut rsvp: abi::Reservation =
+            sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = $1 FOR UPDATE")
+                .bind(request.id)
+                .fetch_one(&mut tx)
+                .await?;
+        let resource_id = rsvp.resource_id.clone();
+        request.apply(&mut rsvp)?;
+        if rsvp.resource_id != resource_id {
+            check_resource(&mut tx, &rsvp.resource_id).await?;
+        }
+
+        // the exclusion constraint rejects the change if it conflicts with others
+        let rsvp: abi::Reservation = sqlx::query_as(
+            "UPDATE rsvp.reservations SET user_id = $1, resource_id = $2, timespan = $3, note = $4 WHERE id = $5 RETURNING *",
+        )
+        .bind(&rsvp.user_id)
+        .bind(&rsvp.resource_id)
+        .bind(rsvp.get_timespan())
+        .bind(&rsvp.note)
+        .bind(rsvp.id)
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
+    async fn reschedule(
+        &self,
+        request: abi::RescheduleRequest,
+    ) -> Result<abi::Reservation, abi::Error> {
+        request.validate()?;
+        let mut tx = self.begin().await?;
+        // the exclusion constraint rejects the new timespan if it conflicts with others
+        let rsvp: abi::Reservation =
+            sqlx::query_as("UPDATE rsvp.reservations SET timespan = $1 WHERE id = $2 RETURNING *")
+                .bind(request.get_timespan())
+                .bind(request.id)
+                .fetch_one(&mut tx)
                 .await?;
+        tx.commit().await?;
         Ok(rsvp)
     }
     async fn get(&self, id: crate::ReservationId) -> Result<abi::Reservation, abi::Error> {
@@ -83,11 +136,51 @@ impl Rsvp for ReservationManager {
     async fn delete(&self, id: crate::ReservationId) -> Result<abi::Reservation, abi::Error> {
         // delete reservation by id
         id.validate()?;
+        let mut tx = self.begin().await?;
         let rsvp: abi::Reservation =
             sqlx::query_as("DELETE FROM rsvp.reservations WHERE id = $1 RETURNING *")
                 .bind(id)
-                .fetch_one(&self.pool)
+                .fetch_one(&mut tx)
                 .await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
+
+    async fn cancel(&self, request: abi::CancelRequest) -> Result<abi::Reservation, abi::Error> {
+        request.id.validate()?;
+        let mut tx = self.begin_cancel(&request.cancelled_by).await?;
+        let rsvp: abi::Reservation = sqlx::query_as(
+            "UPDATE rsvp.reservations SET status = 'cancelled', cancel_reason = $2, cancelled_at = now(), cancelled_by = $3 WHERE id = $1 AND status <> 'cancelled' RETURNING *",
+        )
+        .bind(request.id)
+        .bind(string_to_option(&request.reason))
+        .bind(string_to_option(&request.cancelled_by))
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
+
+    async fn restore(&self, id: crate::ReservationId) -> Result<abi::Reservation, abi::Error> {
+        id.validate()?;
+        let mut tx = self.begin().await?;
+        let rsvp: abi::Reservation = sqlx::query_as(
+            "SELECT * FROM rsvp.reservations WHERE id = $1 AND status = 'cancelled' FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_one(&mut tx)
+        .await?;
+        check_resource(&mut tx, &rsvp.resource_id).await?;
+
+        // back to the status before it's cancelled, pending if unknown. The capacity trigger
+        // rejects it if the window is taken by others meanwhile
+        let rsvp: abi::Reservation = sqlx::query_as(
+            "UPDATE rsvp.reservations SET status = COALESCE((SELECT (snapshot->>'status')::rsvp.reservation_status FROM rsvp.reservation_history WHERE reservation_id = $1 AND snapshot->>'status' <> 'cancelled' ORDER BY id DESC LIMIT 1), 'pending'), cancel_reason = NULL, cancelled_at = NULL, cancelled_by = NULL WHERE id = $1 RETURNING *",
+        )
+        .bind(id)
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
         Ok(rsvp)
     }
 
@@ -100,6 +193,7 @@ impl Rsvp for ReservationManager {
         // let range = query.get_timespan();
         let start = query.start.map(|v| convert_to_utc_time(&v));
         let end = query.end.map(|v| convert_to_utc_time(&v));
+        let as_of = query.as_of.map(|v| convert_to_utc_time(&v));
         let status = abi::ReservationStatus::from_i32(query.status)
             .unwrap_or(abi::ReservationStatus::Pending);
 
@@ -108,7 +202,7 @@ impl Rsvp for ReservationManager {
 
         tokio::spawn(async move {
             let mut rsvps = sqlx::query_as(
-                "SELECT * FROM rsvp.query($1,$2,$3,$4,$5::rsvp.reservation_status,$6)",
+                "SELECT * FROM rsvp.query($1,$2,$3,$4,$5::rsvp.reservation_status,$6,$7)",
             )
             .bind(user_id)
             .bind(resource_id)
@@ -116,6 +210,7 @@ impl Rsvp for ReservationManager {
             .bind(end)
             .bind(status.to_string())
             .bind(query.desc)
+            .bind(as_of)
             .fetch_many(&pool);
             while let Some(ret) = rsvps.next().await {
                 match ret {
@@ -156,11 +251,115 @@ impl Rsvp for ReservationManager {
 
         Ok((pager, rsvps.into_iter().collect()))
     }
+
+    async fn listen(
+        &self,
+        request: abi::ListenRequest,
+    ) -> Result<mpsc::Receiver<Result<abi::ListenResponse, abi::Error>>, abi::Error> {
+        self.listen_changes(request).await
+    }
+
+    async fn ack(&self, group: String, id: i64) -> Result<i64, abi::Error> {
+        if group.is_empty() {
+            return Err(abi::Error::InvalidConsumerGroup(group));
+        }
+        if id < 0 {
+            return Err(abi::Error::InvalidCursor(id));
+        }
+        // acknowledged offset could only move forward
+        let offset = sqlx::query(
+            "UPDATE rsvp.consumer_groups SET acked_id = GREATEST(acked_id, $2), update_at = now() WHERE name = $1 RETURNING acked_id",
+        )
+        .bind(group)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?
+        .get(0);
+        Ok(offset)
+    }
+
+    async fn live_query(
+        &self,
+        query: abi::ReservationQuery,
+    ) -> Result<mpsc::Receiver<Result<abi::LiveQueryEvent, abi::Error>>, abi::Error> {
+        let (live_query, snapshot) = LiveQuery::snapshot(&self.pool, query).await?;
+        let request = abi::ListenRequest {
+            offset: Some(live_query.offset()),
+            ..Default::default()
+        };
+        let changes = self.listen_changes(request).await?;
+        let (tx, rx) = mpsc::channel(128);
+
+        tokio::spawn(live_query.run(snapshot, changes, tx));
+        Ok(rx)
+    }
 }
 
 impl ReservationManager {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        let config = abi::ChangesConfig::default();
+        Self {
+            hub: Arc::new(ChangeHub::new(pool.clone(), config.listen_buffer)),
+            pool,
+            ack_timeout: Duration::from_secs(config.ack_timeout),
+            actor: None,
+        }
+    }
+    /// a manager making the changes on behalf of the actor, e.g. a user or an admin
+    pub fn acting_as(&self, actor: impl Into<String>) -> Self {
+        Self {
+            actor: Some(actor.into()),
+            ..self.clone()
+        }
+    }
+    /// begin a transaction, the reservation changes in it are recorded as made by the actor
+    pub(crate) async fn begin(&self) -> Result<Transaction<'static, Postgres>, abi::Error> {
+        let mut tx = self.pool.begin().await?;
+        if let Some(actor) = &self.actor {
+            sqlx::query("SELECT set_config('rsvp.actor', $1, true)")
+                .bind(actor)
+                .execute(&mut tx)
+                .await?;
+        }
+        Ok(tx)
+    }
+    /// begin a transaction to cancel reservations, the one cancelling is the actor unless it's
+    /// already known
+    pub(crate) async fn begin_cancel(
+        &self,
+        cancelled_by: &str,
+    ) -> Result<Transaction<'static, Postgres>, abi::Error> {
+        match &self.actor {
+            None if !cancelled_by.is_empty() => self.acting_as(cancelled_by).begin().await,
+            _ => self.begin().await,
+        }
+    }
+    /// set how long to wait for a consumer group to acknowledge the changes before redelivering
+    pub fn with_ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.ack_timeout = ack_timeout;
+        self
+    }
+    /// set how many changes could be buffered for each listener before it's disconnected
+    pub fn with_listen_buffer(mut self, listen_buffer: usize) -> Self {
+        self.hub = Arc::new(ChangeHub::new(self.pool.clone(), listen_buffer));
+        self
+    }
+    /// stream the reservation changes as any type that could be built from them
+    pub(crate) async fn listen_changes<T>(
+        &self,
+        request: abi::ListenRequest,
+    ) -> Result<mpsc::Receiver<Result<T, abi::Error>>, abi::Error>
+    where
+        T: From<abi::ReservationChange> + Send + 'static,
+    {
+        request.validate()?;
+
+        let listener =
+            ChangeListener::new(&self.hub, self.pool.clone(), request, self.ack_timeout).await?;
+        let (tx, rx) = mpsc::channel(128);
+
+        tokio::spawn(listener.run(tx));
+        Ok(rx)
     }
     pub async fn from_config(config: &DbConfig) -> Result<Self, abi::Error> {
         let url = config.url();
@@ -172,15 +371,76 @@ impl ReservationManager {
     }
 }
 
-fn str_to_option(s: &str) -> Option<&str> {
-    if s.is_empty() {
-        None
-    } else {
-        Some(s)
+/// insert all the validated reservations, report every conflict if any of them conflicts.
+/// The transaction should be dropped on error
+pub(crate) async fn insert_all(
+    tx: &mut Transaction<'_, Postgres>,
+    rsvps: Vec<abi::Reservation>,
+) -> Result<Vec<abi::Reservation>, abi::Error> {
+    // the resources are locked in the same order by every batch, so two batches never wait on
+    // each other
+    let resource_ids: BTreeSet<_> = rsvps.iter().map(|r| r.resource_id.as_str()).collect();
+    for id in resource_ids {
+        check_resource(tx, id).await?;
     }
+
+    let mut reserved = Vec::with_capacity(rsvps.len());
+    let mut conflicts = Vec::new();
+    for rsvp in rsvps {
+        // a conflict only rolls back to the savepoint, so the rest could still be checked
+        let mut savepoint = tx.begin().await?;
+        match insert_reservation(&mut savepoint, rsvp).await {
+            Ok(rsvp) => {
+                savepoint.commit().await?;
+                reserved.push(rsvp);
+            }
+            Err(abi::Error::ConflictReservation(info)) => {
+                savepoint.rollback().await?;
+                conflicts.push(info);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(abi::Error::ConflictReservations(conflicts));
+    }
+    Ok(reserved)
 }
 
-fn string_to_option(s: &str) -> Option<String> {
+/// insert a validated reservation, fill its id
+pub(crate) async fn insert_reservation(
+    conn: &mut PgConnection,
+    mut rsvp: abi::Reservation,
+) -> Result<abi::Reservation, abi::Error> {
+    check_resource(&mut *conn, &rsvp.resource_id).await?;
+
+    let status =
+        abi::ReservationStatus::from_i32(rsvp.status).unwrap_or(abi::ReservationStatus::Pending);
+
+    let timespan = rsvp.get_timespan();
+
+    // generate a insert sql for the reservation
+    // execute the sql
+    let id = sqlx::query(
+        "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status, series_id, recurrence_id) VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status, $6, $7) RETURNING id"
+    )
+    .bind(rsvp.user_id.clone())
+    .bind(rsvp.resource_id.clone())
+    .bind(timespan)
+    .bind(rsvp.note.clone())
+    .bind(status.to_string())
+    .bind((rsvp.series_id != 0).then_some(rsvp.series_id))
+    .bind(rsvp.recurrence_id.as_ref().map(convert_to_utc_time))
+    .fetch_one(conn)
+    .await?.get(0);
+
+    rsvp.id = id;
+
+    Ok(rsvp)
+}
+
+pub(crate) fn string_to_option(s: &str) -> Option<String> {
     if s.is_empty() {
         None
     } else {
@@ -191,11 +451,13 @@ fn string_to_option(s: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use abi::{
-        Reservation, ReservationConflict, ReservationConflictInfo, ReservationFilterBuilder,
-        ReservationQueryBuilder, ReservationWindow,
+        convert_to_timestamp, Reservation, ReservationConflict, ReservationConflictInfo,
+        ReservationFilterBuilder, ReservationQueryBuilder, ReservationWindow,
     };
+    use chrono::{DateTime, FixedOffset, Utc};
 
     use super::*;
+    use crate::test_utils::test_manager;
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn reserve_should_work_for_valid_window() {
@@ -204,7 +466,7 @@ mod tests {
     }
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn reserve_conflict_should_reject() {
-        let (_, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        let (rsvp1, manager) = make_kyros_reservation(migrated_pool.clone()).await;
 
         let rsvp2 = abi::Reservation::new_pending(
             "alice",
@@ -215,7 +477,7 @@ mod tests {
         );
         let err = manager.reserve(rsvp2).await.unwrap_err();
 
-        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
             new: ReservationWindow {
                 rid: "ocean-view-room-417".to_string(),
                 start: "2022-12-26T15:00:00-0700".parse().unwrap(),
@@ -226,9 +488,189 @@ mod tests {
                 start: "2022-12-25T15:00:00-0700".parse().unwrap(),
                 end: "2022-12-28T12:00:00-0700".parse().unwrap(),
             },
-        });
+            capacity: 1,
+            taken: vec![rsvp1.id],
+        }));
+
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_should_change_masked_fields() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
+
+        let mut partial = abi::Reservation::new_pending(
+            "bob",
+            "ixia-test-2",
+            "2023-03-25T15:00:00-0700".parse().unwrap(),
+            "2023-04-25T12:00:00-0700".parse().unwrap(),
+            "moved to the next quarter",
+        );
+        partial.id = rsvp.id;
+        let request = abi::UpdateRequest::new(
+            rsvp.id,
+            partial.clone(),
+            &["resource_id", "start", "end", "note"],
+        );
+        let updated = manager.update(request).await.unwrap();
+        assert_eq!(updated.user_id, rsvp.user_id);
+        assert_eq!(updated.resource_id, partial.resource_id);
+        assert_eq!(updated.start, partial.start);
+        assert_eq!(updated.end, partial.end);
+        assert_eq!(updated.note, partial.note);
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Update as i32);
+        assert_eq!(change.old, Some(rsvp));
+        assert_eq!(change.new, Some(updated));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_conflict_should_reject() {
+        let (_, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        let (rsvp, _) = make_alice_reservation(migrated_pool.clone()).await;
+
+        let mut partial = rsvp.clone();
+        partial.resource_id = "ocean-view-room-417".into();
+        partial.start = Some("2022-12-26T15:00:00-0700".parse().unwrap());
+        partial.end = Some("2022-12-30T12:00:00-0700".parse().unwrap());
+        let request = abi::UpdateRequest::new(rsvp.id, partial, &["resource_id", "start", "end"]);
+        let err = manager.update(request).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_note_should_be_recorded() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
+
+        let updated = manager.update_note(rsvp.id, "hello".into()).await.unwrap();
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.old, Some(rsvp));
+        assert_eq!(change.new, Some(updated));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_should_work() {
+        let (rsvp, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
 
+        let start: DateTime<FixedOffset> = "2022-12-29T15:00:00-0700".parse().unwrap();
+        let end: DateTime<FixedOffset> = "2022-12-31T12:00:00-0700".parse().unwrap();
+        let request = abi::RescheduleRequest::new(
+            rsvp.id,
+            convert_to_timestamp(&start.with_timezone(&Utc)),
+            convert_to_timestamp(&end.with_timezone(&Utc)),
+        );
+        let rescheduled = manager.reschedule(request.clone()).await.unwrap();
+        assert_eq!(rescheduled.id, rsvp.id);
+        assert_eq!(rescheduled.start, request.start);
+        assert_eq!(rescheduled.end, request.end);
+        assert_eq!(rescheduled.note, rsvp.note);
+
+        // reschedule is recorded as an update
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Update as i32);
+        assert_eq!(change.old, Some(rsvp));
+        assert_eq!(change.new, Some(rescheduled));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_conflict_should_reject() {
+        let (rsvp1, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        let (rsvp, _) = make_reservation(
+            migrated_pool.clone(),
+            "alice",
+            "ocean-view-room-417",
+            "2022-12-29T15:00:00-0700",
+            "2022-12-31T12:00:00-0700",
+            "",
+        )
+        .await;
+
+        let start: DateTime<FixedOffset> = "2022-12-26T15:00:00-0700".parse().unwrap();
+        let end: DateTime<FixedOffset> = "2022-12-30T12:00:00-0700".parse().unwrap();
+        let request = abi::RescheduleRequest::new(
+            rsvp.id,
+            convert_to_timestamp(&start.with_timezone(&Utc)),
+            convert_to_timestamp(&end.with_timezone(&Utc)),
+        );
+        let err = manager.reschedule(request).await.unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: ReservationWindow {
+                rid: "ocean-view-room-417".to_string(),
+                start: "2022-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2022-12-30T12:00:00-0700".parse().unwrap(),
+            },
+            old: ReservationWindow {
+                rid: "ocean-view-room-417".to_string(),
+                start: "2022-12-25T15:00:00-0700".parse().unwrap(),
+                end: "2022-12-28T12:00:00-0700".parse().unwrap(),
+            },
+            capacity: 1,
+            taken: vec![rsvp1.id],
+        }));
         assert_eq!(err, abi::Error::ConflictReservation(info));
+
+        // the reservation is kept untouched
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_invalid_time_should_reject() {
+        let (rsvp, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        let request = abi::RescheduleRequest::new(rsvp.id, rsvp.end.unwrap(), rsvp.start.unwrap());
+        let err = manager.reschedule(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_many_should_work() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let rsvps = make_daily_reservations("alice", "ocean-view-room-417", 20, 5);
+
+        let reserved = manager.reserve_many(rsvps.clone()).await.unwrap();
+        assert_eq!(reserved.len(), 5);
+        for (rsvp, expected) in reserved.iter().zip(rsvps) {
+            assert!(rsvp.id != 0);
+            assert_eq!(rsvp.start, expected.start);
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_many_conflict_should_rollback_and_report_all() {
+        let (_, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+        // 12-23 to 12-28, the last three days conflict with kyros
+        let rsvps = make_daily_reservations("alice", "ocean-view-room-417", 23, 5);
+
+        let err = manager.reserve_many(rsvps).await.unwrap_err();
+        let conflicts = match err {
+            abi::Error::ConflictReservations(conflicts) => conflicts,
+            e => panic!("unexpected error: {e:?}"),
+        };
+        assert_eq!(conflicts.len(), 3);
+        for conflict in conflicts {
+            let conflict = match conflict {
+                ReservationConflictInfo::Parsed(conflict) => conflict,
+                info => panic!("unparsed conflict: {info:?}"),
+            };
+            assert_eq!(
+                conflict.old.start,
+                "2022-12-25T15:00:00-0700".parse::<DateTime<Utc>>().unwrap()
+            );
+        }
+
+        // none of the batch is made
+        let filter = ReservationFilterBuilder::default()
+            .user_id("alice")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert!(rsvps.is_empty());
     }
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
@@ -279,6 +721,158 @@ mod tests {
         assert_eq!(rsvp1, abi::Error::NotFound);
     }
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn cancel_reservation_should_keep_it_and_free_the_window() {
+        let (rsvp, manager) = make_kyros_reservation(migrated_pool.clone()).await;
+
+        let request = abi::CancelRequest::new(rsvp.id).with_reason("flight cancelled", "kyros");
+        let cancelled = manager.cancel(request).await.unwrap();
+        assert_eq!(cancelled.status, abi::ReservationStatus::Cancelled as i32);
+        assert_eq!(cancelled.cancel_reason, "flight cancelled");
+        assert_eq!(cancelled.cancelled_by, "kyros");
+        assert!(cancelled.cancelled_at.is_some());
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), cancelled);
+
+        // cancelling it again finds nothing to cancel
+        let err = manager
+            .cancel(abi::CancelRequest::new(rsvp.id))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        // the window is free for others, so it can't be restored until they cancel
+        let mut other = rsvp.clone();
+        other.user_id = "alice".into();
+        let other = manager.reserve(other).await.unwrap();
+        let err = manager.restore(rsvp.id).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        manager
+            .cancel(abi::CancelRequest::new(other.id))
+            .await
+            .unwrap();
+        let restored = manager.restore(rsvp.id).await.unwrap();
+        assert_eq!(restored.status, abi::ReservationStatus::Pending as i32);
+        assert_eq!(restored.cancel_reason, "");
+        assert_eq!(restored.cancelled_at, None);
+        assert_eq!(restored.start, rsvp.start);
+
+        // only cancelled reservations could be restored
+        let err = manager.restore(rsvp.id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn restore_should_keep_the_status_before_cancelled() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        manager.change_status(rsvp.id).await.unwrap();
+        manager
+            .cancel(abi::CancelRequest::new(rsvp.id))
+            .await
+            .unwrap();
+
+        let restored = manager.restore(rsvp.id).await.unwrap();
+        assert_eq!(restored.status(), abi::ReservationStatus::Confirmed);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_cancelled_reservation_should_reject() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rsvp = abi::Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "",
+        );
+        rsvp.status = abi::ReservationStatus::Cancelled as i32;
+        let err = manager.reserve(rsvp).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(abi::ReservationStatus::Cancelled as i32)
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_cancelled_reservation_should_work() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        manager
+            .cancel(abi::CancelRequest::new(rsvp.id))
+            .await
+            .unwrap();
+
+        let mut src = rsvp.clone();
+        src.note = "see you next time".into();
+        let request = abi::UpdateRequest::new(rsvp.id, src, &["note"]);
+        let rsvp = manager.update(request).await.unwrap();
+        assert_eq!(rsvp.note, "see you next time");
+        assert_eq!(rsvp.status, abi::ReservationStatus::Cancelled as i32);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_as_of_should_rebuild_past_reservations() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        manager.change_status(rsvp.id).await.unwrap();
+        manager
+            .update_note(rsvp.id, "moved to next month".into())
+            .await
+            .unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        // moments right after each change, taken from the database clock
+        let history = manager.history(rsvp.id).await.unwrap();
+        let created = history[0].timestamp.clone().unwrap();
+        let confirmed = history[1].timestamp.clone().unwrap();
+        let deleted = history[3].timestamp.clone().unwrap();
+
+        let query = |status: abi::ReservationStatus, as_of: prost_types::Timestamp| {
+            ReservationQueryBuilder::default()
+                .user_id("alice")
+                .status(status as i32)
+                .as_of(as_of)
+                .build()
+                .unwrap()
+        };
+        let mut rx = manager
+            .query(query(abi::ReservationStatus::Pending, created.clone()))
+            .await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvp.clone())));
+        assert_eq!(rx.recv().await, None);
+
+        let mut rx = manager
+            .query(query(abi::ReservationStatus::Pending, confirmed.clone()))
+            .await;
+        assert_eq!(rx.recv().await, None);
+
+        // the live table has nothing left, while the past still has it
+        let mut rx = manager
+            .query(query(abi::ReservationStatus::Confirmed, deleted.clone()))
+            .await;
+        assert_eq!(rx.recv().await, None);
+
+        let filter = ReservationFilterBuilder::default()
+            .user_id("alice")
+            .status(abi::ReservationStatus::Confirmed as i32)
+            .as_of(confirmed)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps.len(), 1);
+        assert_eq!(rsvps[0].id, rsvp.id);
+        assert_eq!(rsvps[0].status(), abi::ReservationStatus::Confirmed);
+        assert_eq!(rsvps[0].note, rsvp.note);
+        assert_eq!(rsvps[0].start, rsvp.start);
+
+        // nothing was there before it's created
+        let before = prost_types::Timestamp {
+            seconds: created.seconds - 1,
+            nanos: 0,
+        };
+        let mut rx = manager
+            .query(query(abi::ReservationStatus::Pending, before))
+            .await;
+        assert_eq!(rx.recv().await, None);
+    }
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn query_reservation_should_work() {
         let (mut rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
 
@@ -362,7 +956,303 @@ mod tests {
         assert_eq!(rsvps[0], rsvp);
     }
 
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_should_receive_reservation_changes() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
+
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        manager.change_status(rsvp.id).await.unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        for op in [
+            abi::ReservationUpdateType::Create,
+            abi::ReservationUpdateType::Update,
+            abi::ReservationUpdateType::Delete,
+        ] {
+            let change = rx.recv().await.unwrap().unwrap();
+            assert_eq!(change.op(), op);
+            assert_eq!(change.reservation().unwrap().id, rsvp.id);
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_should_carry_reservation_snapshots() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
+
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let confirmed = manager.change_status(rsvp.id).await.unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        let created = rx.recv().await.unwrap().unwrap();
+        assert_eq!(created.old, None);
+        assert_eq!(created.new, Some(rsvp.clone()));
+        assert!(created.timestamp.is_some());
+
+        let updated = rx.recv().await.unwrap().unwrap();
+        assert_eq!(updated.old, Some(rsvp));
+        assert_eq!(updated.new, Some(confirmed.clone()));
+
+        // deleted reservation is still visible in the change
+        let deleted = rx.recv().await.unwrap().unwrap();
+        assert_eq!(deleted.old, Some(confirmed));
+        assert_eq!(deleted.new, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_with_offset_should_replay_missed_changes() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        manager.change_status(rsvp.id).await.unwrap();
+
+        // replay everything from the beginning
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                offset: Some(0),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let created = rx.recv().await.unwrap().unwrap();
+        assert_eq!(created.op(), abi::ReservationUpdateType::Create);
+        let updated = rx.recv().await.unwrap().unwrap();
+        assert_eq!(updated.op(), abi::ReservationUpdateType::Update);
+        assert!(updated.id > created.id);
+
+        // resume after the first change, then switch to live changes
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                offset: Some(created.id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.id, updated.id);
+        assert_eq!(change.op(), abi::ReservationUpdateType::Update);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op(), abi::ReservationUpdateType::Delete);
+        assert_eq!(change.reservation().unwrap().id, rsvp.id);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_with_negative_offset_should_reject() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let err = manager
+            .listen(abi::ListenRequest {
+                offset: Some(-1),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidCursor(-1));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_with_group_should_redeliver_unacked_changes() {
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let manager = manager.with_ack_timeout(Duration::from_millis(100));
+        let request = abi::ListenRequest {
+            group: "billing".into(),
+            ..Default::default()
+        };
+
+        // a new group starts from the beginning of the change queue
+        let mut rx = manager.listen(request.clone()).await.unwrap();
+        let created = rx.recv().await.unwrap().unwrap();
+        assert_eq!(created.op(), abi::ReservationUpdateType::Create);
+        assert_eq!(created.reservation().unwrap().id, rsvp.id);
+
+        // not acknowledged in time, so it should be delivered again
+        let redelivered = rx.recv().await.unwrap().unwrap();
+        assert_eq!(redelivered, created);
+        drop(rx);
+
+        let offset = manager.ack("billing".into(), created.id).await.unwrap();
+        assert_eq!(offset, created.id);
+
+        // reconnect: acknowledged changes are skipped
+        let mut rx = manager.listen(request).await.unwrap();
+        manager.change_status(rsvp.id).await.unwrap();
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op(), abi::ReservationUpdateType::Update);
+        assert!(change.id > created.id);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn ack_should_not_move_offset_backwards() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let err = manager.ack("billing".into(), 1).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        let _rx = manager
+            .listen(abi::ListenRequest {
+                group: "billing".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(manager.ack("billing".into(), 5).await.unwrap(), 5);
+        assert_eq!(manager.ack("billing".into(), 3).await.unwrap(), 5);
+
+        let err = manager.ack("".into(), 3).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidConsumerGroup("".into()));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_with_filter_should_only_receive_matched_changes() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                resource_pattern: "ocean-view-*".into(),
+                ops: vec![abi::ReservationUpdateType::Create as i32],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let (alice, _) = make_alice_reservation(migrated_pool.clone()).await;
+        let (kyros, _) = make_kyros_reservation(migrated_pool.clone()).await;
+        manager.change_status(kyros.id).await.unwrap();
+        manager.delete(alice.id).await.unwrap();
+        let (rsvp, _) = make_reservation(
+            migrated_pool.clone(),
+            "alice",
+            "ocean-view-room-713",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation().unwrap().id, kyros.id);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation().unwrap().id, rsvp.id);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listen_with_invalid_op_should_reject() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let err = manager
+            .listen(abi::ListenRequest {
+                ops: vec![10],
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidUpdateType(10));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn live_query_should_send_snapshot_then_deltas() {
+        let (alice, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let query = abi::ReservationQueryBuilder::default()
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.live_query(query).await.unwrap();
+
+        let event = rx.recv().await.unwrap().unwrap();
+        assert_eq!(event.r#type, abi::LiveQueryEventType::Snapshot as i32);
+        assert_eq!(event.reservation, Some(alice.clone()));
+        let event = rx.recv().await.unwrap().unwrap();
+        assert_eq!(event.r#type, abi::LiveQueryEventType::Synced as i32);
+
+        let (kyros, _) = make_kyros_reservation(migrated_pool.clone()).await;
+        let confirmed = manager.change_status(alice.id).await.unwrap();
+        // not in the result set, so no delta
+        manager.delete(alice.id).await.unwrap();
+        manager.delete(kyros.id).await.unwrap();
+
+        let event = rx.recv().await.unwrap().unwrap();
+        assert_eq!(event.r#type, abi::LiveQueryEventType::Insert as i32);
+        assert_eq!(event.reservation, Some(kyros.clone()));
+        let event = rx.recv().await.unwrap().unwrap();
+        assert_eq!(event.r#type, abi::LiveQueryEventType::Remove as i32);
+        assert_eq!(event.reservation.unwrap().id, confirmed.id);
+        let event = rx.recv().await.unwrap().unwrap();
+        assert_eq!(event.r#type, abi::LiveQueryEventType::Remove as i32);
+        assert_eq!(event.reservation, Some(kyros));
+        assert!(event.offset > 0);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn listeners_should_share_one_connection() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut listeners = Vec::new();
+        for _ in 0..10 {
+            listeners.push(manager.listen(abi::ListenRequest::default()).await.unwrap());
+        }
+
+        let count: i64 = sqlx::query("SELECT COUNT(*) FROM pg_stat_activity WHERE datname = current_database() AND query ILIKE 'LISTEN%'")
+            .fetch_one(&migrated_pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(count, 1);
+
+        let (rsvp, _) = make_alice_reservation(migrated_pool.clone()).await;
+        for rx in listeners.iter_mut() {
+            let change = rx.recv().await.unwrap().unwrap();
+            assert_eq!(change.new, Some(rsvp.clone()));
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn slow_listener_should_be_told_to_resume() {
+        let manager = test_manager(migrated_pool.clone())
+            .await
+            .with_listen_buffer(1);
+        let mut rx = manager.listen(abi::ListenRequest::default()).await.unwrap();
+
+        // more changes than the listener could buffer
+        for i in 0..200 {
+            let rid = format!("room-{i}");
+            manager
+                .create_resource(abi::Resource::new(&rid, &rid, "room"))
+                .await
+                .unwrap();
+            make_reservation(
+                migrated_pool.clone(),
+                "alice",
+                &rid,
+                "2023-01-25T15:00:00-0700",
+                "2023-02-25T12:00:00-0700",
+                "",
+            )
+            .await;
+        }
+
+        let mut last = 0;
+        let err = loop {
+            match rx.recv().await.unwrap() {
+                Ok(change) => last = change.id,
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(err, abi::Error::ListenLagged(last));
+        assert!(rx.recv().await.is_none());
+    }
+
     // private none test functions
+    fn make_daily_reservations(uid: &str, rid: &str, from_day: u32, days: u32) -> Vec<Reservation> {
+        (from_day..from_day + days)
+            .map(|day| {
+                abi::Reservation::new_pending(
+                    uid,
+                    rid,
+                    format!("2022-12-{day:02}T15:00:00-0700").parse().unwrap(),
+                    format!("2022-12-{:02}T12:00:00-0700", day + 1)
+                        .parse()
+                        .unwrap(),
+                    "",
+                )
+            })
+            .collect()
+    }
     async fn make_alice_reservation(pool: PgPool) -> (Reservation, ReservationManager) {
         make_reservation(
             pool,
@@ -393,7 +1283,7 @@ mod tests {
         end: &str,
         note: &str,
     ) -> (Reservation, ReservationManager) {
-        let manager = ReservationManager::new(pool.clone());
+        let manager = test_manager(pool.clone()).await;
         let rsvp = abi::Reservation::new_pending(
             uid,
             rid,
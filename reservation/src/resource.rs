@@ -0,0 +1,478 @@
+use abi::{Normalizer, Resource, Validator};
+use sqlx::{postgres::PgDatabaseError, PgConnection};
+
+use crate::ReservationManager;
+
+impl ReservationManager {
+    /// add a resource which could be reserved
+    pub async fn create_resource(&self, mut resource: Resource) -> Result<Resource, abi::Error> {
+        resource.normalize()?;
+
+        let id = resource.id.clone();
+        let (before, after) = resource.buffers();
+        let resource = sqlx::query_as(
+            "INSERT INTO rsvp.resources (id, name, resource_type, tags, timezone, capacity, retired, buffer_before, buffer_after) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *",
+        )
+        .bind(resource.id)
+        .bind(resource.name)
+        .bind(resource.resource_type)
+        .bind(resource.tags)
+        .bind(resource.timezone)
+        .bind(resource.capacity)
+        .bind(resource.retired)
+        .bind(before)
+        .bind(after)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match db_error_code(&e) {
+            Some("23505") => abi::Error::ResourceExists(id),
+            _ => e.into(),
+        })?;
+        Ok(resource)
+    }
+
+    /// get resource by id
+    pub async fn get_resource(&self, id: &str) -> Result<Resource, abi::Error> {
+        sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| abi::Error::UnknownResource(id.into()))
+    }
+
+    /// update the resource fields listed in the update mask. Fewer seats or longer buffers are
+    /// rejected if the reservations not ended yet wouldn't fit them
+    pub async fn update_resource(
+        &self,
+        request: abi::UpdateResourceRequest,
+    ) -> Result<Resource, abi::Error> {
+        request.validate()?;
+
+        let mut tx = self.begin().await?;
+        // the capacity lock is taken before the row like check_resource does, the row lock
+        // leaves the foreign key checks of the reservations alone
+        lock_resource(&mut tx, &request.id).await?;
+        let mut resource: Resource =
+            sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1 FOR NO KEY UPDATE")
+                .bind(&request.id)
+                .fetch_optional(&mut tx)
+                .await?
+                .ok_or_else(|| abi::Error::UnknownResource(request.id.clone()))?;
+        let (capacity, (old_before, old_after)) = (resource.capacity, resource.buffers());
+        request.apply(&mut resource)?;
+
+        let (before, after) = resource.buffers();
+        let recheck = resource.capacity < capacity || before > old_before || after > old_after;
+        let resource: Resource = sqlx::query_as(
+            "UPDATE rsvp.resources SET name = $2, resource_type = $3, tags = $4, timezone = $5, capacity = $6, retired = $7, buffer_before = $8, buffer_after = $9, update_at = now() WHERE id = $1 RETURNING *",
+        )
+        .bind(resource.id)
+        .bind(resource.name)
+        .bind(resource.resource_type)
+        .bind(resource.tags)
+        .bind(resource.timezone)
+        .bind(resource.capacity)
+        .bind(resource.retired)
+        .bind(before)
+        .bind(after)
+        .fetch_one(&mut tx)
+        .await?;
+
+        if recheck {
+            let overbooked: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM rsvp.reservations WHERE resource_id = $1 AND status <> 'cancelled' AND upper(timespan) >= now() AND rsvp.seats_taken($1, timespan) > $2)",
+            )
+            .bind(&request.id)
+            .bind(resource.capacity)
+            .fetch_one(&mut tx)
+            .await?;
+            if overbooked {
+                return Err(abi::Error::ResourceInUse(request.id));
+            }
+        }
+        tx.commit().await?;
+        Ok(resource)
+    }
+
+    /// delete a resource, resources with reservations should be retired instead
+    pub async fn delete_resource(&self, id: &str) -> Result<Resource, abi::Error> {
+        sqlx::query_as("DELETE FROM rsvp.resources WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| match db_error_code(&e) {
+                Some("23503") => abi::Error::ResourceInUse(id.into()),
+                _ => e.into(),
+            })?
+            .ok_or_else(|| abi::Error::UnknownResource(id.into()))
+    }
+
+    /// list resources order by resource id
+    pub async fn list_resources(&self, include_retired: bool) -> Result<Vec<Resource>, abi::Error> {
+        let resources =
+            sqlx::query_as("SELECT * FROM rsvp.resources WHERE $1 OR NOT retired ORDER BY id")
+                .bind(include_retired)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(resources)
+    }
+}
+
+/// make sure the resource could be reserved, it can't be retired until the transaction ends
+pub(crate) async fn check_resource(conn: &mut PgConnection, id: &str) -> Result<(), abi::Error> {
+    lock_resource(&mut *conn, id).await?;
+    let retired: Option<bool> =
+        sqlx::query_scalar("SELECT retired FROM rsvp.resources WHERE id = $1 FOR SHARE")
+            .bind(id)
+            .fetch_optional(conn)
+            .await?;
+    match retired {
+        None => Err(abi::Error::UnknownResource(id.into())),
+        Some(true) => Err(abi::Error::RetiredResource(id.into())),
+        Some(false) => Ok(()),
+    }
+}
+
+/// take the capacity lock of the resource until the transaction ends. It's always taken before
+/// the resource row, so the writers on the same resource never wait on each other in a cycle
+pub(crate) async fn lock_resource(conn: &mut PgConnection, id: &str) -> Result<(), abi::Error> {
+    sqlx::query("SELECT rsvp.lock_resource($1)")
+        .bind(id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+fn db_error_code(e: &sqlx::Error) -> Option<&str> {
+    match e {
+        sqlx::Error::Database(e) => Some(e.downcast_ref::<PgDatabaseError>().code()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::{
+        convert_to_pb_duration, convert_to_timestamp, convert_to_utc_time, Reservation,
+        ReservationConflictInfo, UpdateRequest, UpdateResourceRequest,
+    };
+    use chrono::{DateTime, Duration, Utc};
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn create_resource_should_normalize_and_reject_duplicates() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let resource = manager
+            .create_resource(Resource {
+                id: "desk-42".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(resource.name, "desk-42");
+        assert_eq!(resource.capacity, 1);
+        assert_eq!(manager.get_resource("desk-42").await.unwrap(), resource);
+
+        let err = manager.create_resource(resource).await.unwrap_err();
+        assert_eq!(err, abi::Error::ResourceExists("desk-42".into()));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_unknown_resource_should_reject() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let err = manager
+            .reserve(make_reservation("no-such-room"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("no-such-room".into()));
+
+        // moving a reservation to an unknown resource is rejected as well
+        let rsvp = manager
+            .reserve(make_reservation("ocean-view-room-417"))
+            .await
+            .unwrap();
+        let request =
+            UpdateRequest::new(rsvp.id, make_reservation("no-such-room"), &["resource_id"]);
+        let err = manager.update(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("no-such-room".into()));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn retired_resource_should_not_be_reserved() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let rsvp = manager
+            .reserve(make_reservation("ocean-view-room-417"))
+            .await
+            .unwrap();
+
+        let mut resource = manager.get_resource("ocean-view-room-417").await.unwrap();
+        resource.retired = true;
+        let request = UpdateResourceRequest::new(&resource.id, resource.clone(), &["retired"]);
+        assert_eq!(manager.update_resource(request).await.unwrap(), resource);
+
+        let err = manager
+            .reserve(make_reservation("ocean-view-room-417"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::RetiredResource("ocean-view-room-417".into())
+        );
+
+        // existing reservations are kept, so the resource can't be deleted
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+        let err = manager
+            .delete_resource("ocean-view-room-417")
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::ResourceInUse("ocean-view-room-417".into()));
+        assert!(!manager
+            .list_resources(false)
+            .await
+            .unwrap()
+            .contains(&resource));
+        assert!(manager
+            .list_resources(true)
+            .await
+            .unwrap()
+            .contains(&resource));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_resource_should_keep_the_reservations_fitting() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut lab = Resource::new("lab-1", "Lab", "lab");
+        lab.capacity = 2;
+        let lab = manager.create_resource(lab).await.unwrap();
+
+        // only the reservations not ended yet are checked
+        let tomorrow = Utc::now() + Duration::days(1);
+        for (start, end) in [(0, 60), (30, 90), (100, 130)] {
+            let rsvp = Reservation::new_pending(
+                "alice",
+                "lab-1",
+                (tomorrow + Duration::minutes(start)).into(),
+                (tomorrow + Duration::minutes(end)).into(),
+                "",
+            );
+            manager.reserve(rsvp).await.unwrap();
+        }
+
+        let mut partial = lab.clone();
+        partial.capacity = 1;
+        let request = UpdateResourceRequest::new("lab-1", partial.clone(), &["capacity"]);
+        let err = manager.update_resource(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::ResourceInUse("lab-1".into()));
+        assert_eq!(manager.get_resource("lab-1").await.unwrap(), lab);
+
+        // an hour after the first reservation takes it over the last one
+        partial.buffer_after = Some(convert_to_pb_duration(&Duration::hours(1)));
+        let request = UpdateResourceRequest::new("lab-1", partial.clone(), &["buffer_after"]);
+        let err = manager.update_resource(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::ResourceInUse("lab-1".into()));
+        partial.buffer_after = Some(convert_to_pb_duration(&Duration::minutes(30)));
+        let request = UpdateResourceRequest::new("lab-1", partial, &["buffer_after"]);
+        let lab = manager.update_resource(request).await.unwrap();
+        assert_eq!(lab.buffers().1, Duration::minutes(30));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_resource_should_not_deadlock_with_reserve_many() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let resource = manager.get_resource("ixia-test-2").await.unwrap();
+
+        // batches on both resources in either order, while the second one is updated
+        let tasks: Vec<_> = (0..10)
+            .map(|i| {
+                let manager = manager.clone();
+                let mut resource = resource.clone();
+                tokio::spawn(async move {
+                    if i % 2 == 0 {
+                        resource.capacity = 2 + i;
+                        let request =
+                            UpdateResourceRequest::new("ixia-test-2", resource, &["capacity"]);
+                        return manager.update_resource(request).await.map(|_| ());
+                    }
+                    let start = Utc::now() + Duration::days(i.into());
+                    let mut rsvps: Vec<_> = ["ixia-test-1", "ixia-test-2"]
+                        .iter()
+                        .map(|rid| {
+                            let end = start + Duration::hours(1);
+                            Reservation::new_pending("alice", *rid, start.into(), end.into(), "")
+                        })
+                        .collect();
+                    if i % 4 == 1 {
+                        rsvps.reverse();
+                    }
+                    manager.reserve_many(rsvps).await.map(|_| ())
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_allow_up_to_capacity() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut desks = Resource::new("desks-pool", "Desks", "desk");
+        desks.capacity = 2;
+        manager.create_resource(desks).await.unwrap();
+
+        // 12-25 to 12-26 and 12-27 to 12-28 never overlap, so only one seat is taken at a time
+        let first = reserve_desk(&manager, "2022-12-25", "2022-12-26")
+            .await
+            .unwrap();
+        let second = reserve_desk(&manager, "2022-12-27", "2022-12-28")
+            .await
+            .unwrap();
+        reserve_desk(&manager, "2022-12-25", "2022-12-28")
+            .await
+            .unwrap();
+
+        let err = reserve_desk(&manager, "2022-12-25", "2022-12-25")
+            .await
+            .unwrap_err();
+        let conflict = match err {
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict)) => conflict,
+            e => panic!("unexpected error: {e:?}"),
+        };
+        assert_eq!(conflict.capacity, 2);
+        assert_eq!(conflict.taken, vec![first.id, first.id + 2]);
+        assert_eq!(conflict.old.rid, "desks-pool");
+
+        // the seats are checked against the other reservations when moving one
+        let request = abi::RescheduleRequest::new(
+            second.id,
+            first.start.clone().unwrap(),
+            first.end.clone().unwrap(),
+        );
+        let err = manager.reschedule(request).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn concurrent_reserve_should_not_exceed_capacity() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut parking = Resource::new("parking-lot", "Parking Lot", "parking");
+        parking.capacity = 3;
+        manager.create_resource(parking).await.unwrap();
+
+        let tasks: Vec<_> = (0..10)
+            .map(|i| {
+                let manager = manager.clone();
+                tokio::spawn(async move {
+                    let rsvp = Reservation::new_pending(
+                        format!("user-{i}"),
+                        "parking-lot",
+                        "2022-12-25T09:00:00-0700".parse().unwrap(),
+                        "2022-12-25T18:00:00-0700".parse().unwrap(),
+                        "",
+                    );
+                    manager.reserve(rsvp).await
+                })
+            })
+            .collect();
+
+        let mut reserved = 0;
+        for task in tasks {
+            match task.await.unwrap() {
+                Ok(_) => reserved += 1,
+                Err(abi::Error::ConflictReservation(_)) => {}
+                Err(e) => panic!("unexpected error: {e:?}"),
+            }
+        }
+        assert_eq!(reserved, 3);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_keep_buffers_between_reservations() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let mut lab = Resource::new("lab-1", "Lab", "lab");
+        lab.buffer_before = Some(convert_to_pb_duration(&Duration::minutes(10)));
+        lab.buffer_after = Some(convert_to_pb_duration(&Duration::hours(1)));
+        let lab = manager.create_resource(lab).await.unwrap();
+        assert_eq!(lab.buffers(), (Duration::minutes(10), Duration::hours(1)));
+
+        reserve_lab(&manager, "09:00", "10:00").await.unwrap();
+
+        // 10:30 is within the calibration after the first reservation
+        let err = reserve_lab(&manager, "10:30", "11:00").await.unwrap_err();
+        let conflict = match err {
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict)) => conflict,
+            e => panic!("unexpected error: {e:?}"),
+        };
+        // the conflict is reported against the buffered windows
+        assert_eq!(conflict.new.start, lab_time("10:20"));
+        assert_eq!(conflict.new.end, lab_time("12:00"));
+        assert_eq!(conflict.old.start, lab_time("08:50"));
+        assert_eq!(conflict.old.end, lab_time("11:00"));
+
+        // 11:10 would touch the buffer, while the reservation itself keeps the requested window
+        let rsvp = reserve_lab(&manager, "11:15", "12:00").await.unwrap();
+        assert_eq!(rsvp.start, Some(convert_to_timestamp(&lab_time("11:15"))));
+        assert_eq!(rsvp.end, Some(convert_to_timestamp(&lab_time("12:00"))));
+
+        // free slots leave room for the buffers of both sides
+        let request = abi::AvailabilityRequest::new(
+            "lab-1",
+            lab_time("06:00"),
+            lab_time("18:00"),
+            Duration::minutes(30),
+            None,
+        );
+        let slots: Vec<_> = manager
+            .availability(request)
+            .await
+            .unwrap()
+            .iter()
+            .map(|slot| convert_to_utc_time(slot.start.as_ref().unwrap()))
+            .collect();
+        let after = lab_time("13:10") + Duration::microseconds(1);
+        assert_eq!(slots, vec![lab_time("06:00"), after]);
+    }
+
+    async fn reserve_lab(
+        manager: &ReservationManager,
+        start: &str,
+        end: &str,
+    ) -> Result<Reservation, abi::Error> {
+        let (start, end) = (lab_time(start).into(), lab_time(end).into());
+        let rsvp = Reservation::new_pending("alice", "lab-1", start, end, "");
+        manager.reserve(rsvp).await
+    }
+
+    fn lab_time(s: &str) -> DateTime<Utc> {
+        format!("2023-01-25T{s}:00-0700").parse().unwrap()
+    }
+
+    async fn reserve_desk(
+        manager: &ReservationManager,
+        from: &str,
+        to: &str,
+    ) -> Result<Reservation, abi::Error> {
+        let rsvp = Reservation::new_pending(
+            "alice",
+            "desks-pool",
+            format!("{from}T09:00:00-0700").parse().unwrap(),
+            format!("{to}T18:00:00-0700").parse().unwrap(),
+            "",
+        );
+        manager.reserve(rsvp).await
+    }
+
+    fn make_reservation(rid: &str) -> Reservation {
+        Reservation::new_pending(
+            "alice",
+            rid,
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "",
+        )
+    }
+}
@@ -0,0 +1,125 @@
+use crate::ReservationManager;
+
+/// changes acknowledged by every consumer group (or all of them if there's no group) could be dropped
+const SAFE_HORIZON: &str =
+    "(SELECT COALESCE(MIN(acked_id), 9223372036854775807) FROM rsvp.consumer_groups)";
+
+impl ReservationManager {
+    /// drop the changes out of the retention policy, return how many changes are dropped
+    pub async fn apply_retention(&self, policy: &abi::RetentionConfig) -> Result<u64, abi::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut dropped = 0;
+
+        if let Some(max_age) = policy.max_age {
+            dropped += sqlx::query(&format!(
+                "DELETE FROM rsvp.reservation_changes WHERE id <= {SAFE_HORIZON} AND create_at < now() - $1 * interval '1 second'"
+            ))
+            .bind(max_age as i64)
+            .execute(&mut tx)
+            .await?
+            .rows_affected();
+        }
+
+        if let Some(max_rows) = policy.max_rows {
+            dropped += sqlx::query(&format!(
+                "DELETE FROM rsvp.reservation_changes WHERE id <= {SAFE_HORIZON} AND id <= (SELECT id FROM rsvp.reservation_changes ORDER BY id DESC OFFSET $1 LIMIT 1)"
+            ))
+            .bind(max_rows)
+            .execute(&mut tx)
+            .await?
+            .rows_affected();
+        }
+
+        if let Some(compact_after) = policy.compact_after {
+            // keep only the latest change of each reservation beyond the horizon
+            dropped += sqlx::query(&format!(
+                "DELETE FROM rsvp.reservation_changes c WHERE c.id <= {SAFE_HORIZON} AND c.create_at < now() - $1 * interval '1 second' AND EXISTS (SELECT 1 FROM rsvp.reservation_changes n WHERE n.reservation_id = c.reservation_id AND n.id > c.id)"
+            ))
+            .bind(compact_after as i64)
+            .execute(&mut tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(dropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::RetentionConfig;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn retention_should_keep_unacked_changes() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        // a known consumer which has only acknowledged the first change
+        let _rx = manager
+            .listen(abi::ListenRequest {
+                group: "billing".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let rsvp = make_changes(&manager).await;
+        let ids = change_ids(&migrated_pool, rsvp.id).await;
+        manager.ack("billing".into(), ids[0]).await.unwrap();
+
+        let policy = RetentionConfig {
+            max_rows: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(manager.apply_retention(&policy).await.unwrap(), 1);
+        assert_eq!(change_ids(&migrated_pool, rsvp.id).await, ids[1..]);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn retention_should_compact_old_changes() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let rsvp = make_changes(&manager).await;
+        let ids = change_ids(&migrated_pool, rsvp.id).await;
+
+        let policy = RetentionConfig {
+            compact_after: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(manager.apply_retention(&policy).await.unwrap(), 2);
+        assert_eq!(change_ids(&migrated_pool, rsvp.id).await, ids[2..]);
+
+        let policy = RetentionConfig {
+            max_age: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(manager.apply_retention(&policy).await.unwrap(), 1);
+        assert!(change_ids(&migrated_pool, rsvp.id).await.is_empty());
+    }
+
+    // create, confirm and delete a reservation, which makes 3 changes
+    async fn make_changes(manager: &ReservationManager) -> abi::Reservation {
+        let rsvp = abi::Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        manager.change_status(rsvp.id).await.unwrap();
+        manager.delete(rsvp.id).await.unwrap()
+    }
+
+    async fn change_ids(pool: &PgPool, reservation_id: i64) -> Vec<i64> {
+        sqlx::query_scalar(
+            "SELECT id FROM rsvp.reservation_changes WHERE reservation_id = $1 ORDER BY id",
+        )
+        .bind(reservation_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,170 @@
+use abi::{
+    FindAvailableRequest, Normalizer, Resource, ResourceCandidate, ResourceSearch, Validator,
+};
+use sqlx::{Acquire, FromRow, PgConnection, Row};
+
+use crate::{manager::insert_reservation, ReservationManager};
+
+impl ReservationManager {
+    /// find the resources free for the time window, the best fit first. If reserve is set,
+    /// the best candidate still free is reserved in the same transaction
+    pub async fn find_available(
+        &self,
+        request: FindAvailableRequest,
+    ) -> Result<(Vec<ResourceCandidate>, Option<abi::Reservation>), abi::Error> {
+        request.validate()?;
+        let mut search = request.search.clone().unwrap();
+        search.normalize()?;
+
+        let mut tx = self.begin().await?;
+        let candidates = search_candidates(&mut tx, &search).await?;
+        if !request.reserve {
+            return Ok((candidates, None));
+        }
+
+        let mut reserved = None;
+        for candidate in &candidates {
+            let rid = candidate.resource.as_ref().unwrap().id.clone();
+            // the candidate might be taken since the search, try the next one then
+            let mut savepoint = tx.begin().await?;
+            match insert_reservation(&mut savepoint, request.reservation(rid)).await {
+                Ok(rsvp) => {
+                    savepoint.commit().await?;
+                    reserved = Some(rsvp);
+                    break;
+                }
+                Err(abi::Error::ConflictReservation(_)) => savepoint.rollback().await?,
+                Err(e) => return Err(e),
+            }
+        }
+        let rsvp = reserved.ok_or(abi::Error::NoAvailableResource)?;
+        tx.commit().await?;
+        Ok((candidates, Some(rsvp)))
+    }
+}
+
+/// active resources matching the search which still have a seat for the time window
+pub(crate) async fn search_candidates(
+    conn: &mut PgConnection,
+    search: &ResourceSearch,
+) -> Result<Vec<ResourceCandidate>, abi::Error> {
+    let rows = sqlx::query(
+        "SELECT * FROM (
+            SELECT r.*, rsvp.seats_taken(r.id, $1) AS taken FROM rsvp.resources r
+            WHERE NOT r.retired AND r.tags @> $2 AND r.capacity >= $3
+                AND ($4 = '' OR r.resource_type = $4)
+        ) c WHERE taken < capacity ORDER BY capacity, taken, id LIMIT $5",
+    )
+    .bind(search.get_timespan())
+    .bind(&search.tags)
+    .bind(search.min_capacity)
+    .bind(&search.resource_type)
+    .bind(search.limit as i64)
+    .fetch_all(conn)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(ResourceCandidate {
+                resource: Some(Resource::from_row(row)?),
+                taken: row.try_get("taken")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::ResourceSearchBuilder;
+    use prost_types::Timestamp;
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn find_available_should_rank_candidates() {
+        let manager = make_rooms(migrated_pool.clone()).await;
+        // the smallest room is fully taken, the large one is partially taken
+        for _ in 0..8 {
+            reserve(&manager, "room-8").await.unwrap();
+        }
+        reserve(&manager, "room-20").await.unwrap();
+
+        let request = FindAvailableRequest::new(make_search(8));
+        let (candidates, rsvp) = manager.find_available(request).await.unwrap();
+        assert!(rsvp.is_none());
+        let found: Vec<_> = candidates
+            .iter()
+            .map(|c| (c.resource.as_ref().unwrap().id.as_str(), c.taken))
+            .collect();
+        // room-4 is too small, room-old is retired, room-quiet has no projector
+        assert_eq!(found, vec![("room-12", 0), ("room-20", 1)]);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn find_available_should_reserve_best_candidate() {
+        let manager = make_rooms(migrated_pool.clone()).await;
+        let request = FindAvailableRequest::new(make_search(8)).reserve_for("alice", "review");
+        let (_, rsvp) = manager.find_available(request.clone()).await.unwrap();
+        let rsvp = rsvp.unwrap();
+        assert!(rsvp.id != 0);
+        assert_eq!(rsvp.resource_id, "room-8");
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+
+        // once the best fit is full, the next best is taken
+        for _ in 0..7 {
+            reserve(&manager, "room-8").await.unwrap();
+        }
+        let (_, rsvp) = manager.find_available(request.clone()).await.unwrap();
+        assert_eq!(rsvp.unwrap().resource_id, "room-12");
+
+        let request = FindAvailableRequest::new(make_search(50)).reserve_for("alice", "");
+        let err = manager.find_available(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::NoAvailableResource);
+    }
+
+    async fn make_rooms(pool: sqlx::PgPool) -> ReservationManager {
+        let manager = test_manager(pool).await;
+        for (id, capacity, tags, retired) in [
+            ("room-4", 4, vec!["projector"], false),
+            ("room-8", 8, vec!["projector"], false),
+            ("room-12", 12, vec!["projector", "whiteboard"], false),
+            ("room-20", 20, vec!["projector"], false),
+            ("room-old", 8, vec!["projector"], true),
+            ("room-quiet", 8, vec![], false),
+        ] {
+            let mut resource = Resource::new(id, id, "room");
+            resource.capacity = capacity;
+            resource.tags = tags.into_iter().map(String::from).collect();
+            resource.retired = retired;
+            manager.create_resource(resource).await.unwrap();
+        }
+        manager
+    }
+
+    fn make_search(min_capacity: i32) -> ResourceSearch {
+        ResourceSearchBuilder::default()
+            .tags(vec!["projector".to_string()])
+            .resource_type("room")
+            .min_capacity(min_capacity)
+            .start("2023-01-26T14:00:00-0700".parse::<Timestamp>().unwrap())
+            .end("2023-01-26T15:00:00-0700".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    async fn reserve(
+        manager: &ReservationManager,
+        rid: &str,
+    ) -> Result<abi::Reservation, abi::Error> {
+        let rsvp = abi::Reservation::new_pending(
+            "bob",
+            rid,
+            "2023-01-26T14:00:00-0700".parse().unwrap(),
+            "2023-01-26T15:00:00-0700".parse().unwrap(),
+            "",
+        );
+        manager.reserve(rsvp).await
+    }
+}
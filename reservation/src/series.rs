@@ -0,0 +1,348 @@
+use abi::{
+    convert_to_utc_time, CancelOccurrenceRequest, CancelSeriesRequest, ReservationSeries,
+    ReservationStatus, Validator,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    manager::{insert_all, string_to_option},
+    resource::check_resource,
+    ReservationManager,
+};
+
+impl ReservationManager {
+    /// make a recurring reservation, either all the occurrences are reserved or none of them
+    pub async fn reserve_series(
+        &self,
+        series: ReservationSeries,
+    ) -> Result<(ReservationSeries, Vec<abi::Reservation>), abi::Error> {
+        series.validate()?;
+
+        let mut tx = self.begin().await?;
+        let ret = insert_series(&mut tx, series, ReservationStatus::Pending).await?;
+        tx.commit().await?;
+        Ok(ret)
+    }
+
+    /// cancel a single occurrence, it's excluded from the series so it won't come back
+    pub async fn cancel_occurrence(
+        &self,
+        request: CancelOccurrenceRequest,
+    ) -> Result<abi::Reservation, abi::Error> {
+        request.validate()?;
+        let recurrence_id = convert_to_utc_time(request.recurrence_id.as_ref().unwrap());
+
+        let mut tx = self.begin_cancel(&request.cancelled_by).await?;
+        sqlx::query(
+            "UPDATE rsvp.reservation_series SET exdates = array_append(exdates, $2), update_at = now() WHERE id = $1 RETURNING id",
+        )
+        .bind(request.series_id)
+        .bind(recurrence_id)
+        .fetch_one(&mut tx)
+        .await?;
+        let rsvp = sqlx::query_as(&format!(
+            "{CANCEL_OCCURRENCES} AND recurrence_id = $4 RETURNING *"
+        ))
+        .bind(request.series_id)
+        .bind(string_to_option(&request.reason))
+        .bind(string_to_option(&request.cancelled_by))
+        .bind(recurrence_id)
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rsvp)
+    }
+
+    /// replace the occurrences from recurrence_id on with a new series ("this and following"),
+    /// the occurrences before it stay in the original series
+    pub async fn update_series(
+        &self,
+        series_id: i64,
+        recurrence_id: DateTime<Utc>,
+        series: ReservationSeries,
+    ) -> Result<(ReservationSeries, Vec<abi::Reservation>), abi::Error> {
+        series_id.validate()?;
+        series.validate()?;
+
+        let mut tx = self.begin().await?;
+        let old: ReservationSeries =
+            sqlx::query_as("SELECT * FROM rsvp.reservation_series WHERE id = $1 FOR UPDATE")
+                .bind(series_id)
+                .fetch_one(&mut tx)
+                .await?;
+        let mut rule = old.rule()?;
+        rule.truncate(recurrence_id);
+        // the following occurrences of a recurring block are still blocks
+        let blocked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM rsvp.reservations WHERE series_id = $1 AND status = 'blocked')",
+        )
+        .bind(series_id)
+        .fetch_one(&mut tx)
+        .await?;
+        let status = if blocked {
+            ReservationStatus::Blocked
+        } else {
+            ReservationStatus::Pending
+        };
+
+        sqlx::query(&format!("{CANCEL_OCCURRENCES} AND recurrence_id >= $4"))
+            .bind(series_id)
+            .bind("replaced by the updated series")
+            .bind(&self.actor)
+            .bind(recurrence_id)
+            .execute(&mut tx)
+            .await?;
+        sqlx::query(
+            "UPDATE rsvp.reservation_series SET rrule = $2, update_at = now() WHERE id = $1",
+        )
+        .bind(series_id)
+        .bind(rule.to_string())
+        .execute(&mut tx)
+        .await?;
+
+        let ret = insert_series(&mut tx, series, status).await?;
+        tx.commit().await?;
+        Ok(ret)
+    }
+
+    /// cancel all the active occurrences of a series, the series is kept along with them
+    pub async fn cancel_series(
+        &self,
+        request: CancelSeriesRequest,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        request.series_id.validate()?;
+
+        let mut tx = self.begin_cancel(&request.cancelled_by).await?;
+        sqlx::query("SELECT id FROM rsvp.reservation_series WHERE id = $1 FOR UPDATE")
+            .bind(request.series_id)
+            .fetch_one(&mut tx)
+            .await?;
+        let rsvps: Vec<abi::Reservation> =
+            sqlx::query_as(&format!("{CANCEL_OCCURRENCES} RETURNING *"))
+                .bind(request.series_id)
+                .bind(string_to_option(&request.reason))
+                .bind(string_to_option(&request.cancelled_by))
+                .fetch_all(&mut tx)
+                .await?;
+        // nothing left to cancel
+        if rsvps.is_empty() {
+            return Err(abi::Error::NotFound);
+        }
+        tx.commit().await?;
+        Ok(rsvps)
+    }
+}
+
+/// soft cancel the active occurrences of the series ($1) with the reason ($2) and who ($3)
+const CANCEL_OCCURRENCES: &str = "UPDATE rsvp.reservations SET status = 'cancelled', cancel_reason = $2, cancelled_at = now(), cancelled_by = $3 WHERE series_id = $1 AND status <> 'cancelled'";
+
+/// insert the validated series and all its occurrences with the status
+pub(crate) async fn insert_series(
+    tx: &mut Transaction<'_, Postgres>,
+    mut series: ReservationSeries,
+    status: ReservationStatus,
+) -> Result<(ReservationSeries, Vec<abi::Reservation>), abi::Error> {
+    check_resource(&mut *tx, &series.resource_id).await?;
+
+    let exdates: Vec<DateTime<Utc>> = series.exdates.iter().map(convert_to_utc_time).collect();
+    series.id = sqlx::query_scalar(
+        "INSERT INTO rsvp.reservation_series (user_id, resource_id, rrule, timezone, timespan, note, exdates) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+    )
+    .bind(&series.user_id)
+    .bind(&series.resource_id)
+    .bind(&series.rrule)
+    .bind(&series.timezone)
+    .bind(series.get_timespan())
+    .bind(&series.note)
+    .bind(exdates)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let mut rsvps = series.expand()?;
+    for rsvp in &mut rsvps {
+        rsvp.status = status as i32;
+    }
+    let rsvps = insert_all(tx, rsvps).await?;
+    Ok((series, rsvps))
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::ReservationConflictInfo;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_series_should_work() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let (series, rsvps) = manager
+            .reserve_series(make_weekly_series("FREQ=WEEKLY;COUNT=4"))
+            .await
+            .unwrap();
+        assert!(series.id != 0);
+        assert_eq!(rsvps.len(), 4);
+        for rsvp in &rsvps {
+            assert!(rsvp.id != 0);
+            assert_eq!(rsvp.series_id, series.id);
+            assert_eq!(rsvp.recurrence_id, rsvp.start);
+        }
+
+        // occurrences are read back with their series
+        let rsvp = manager.get(rsvps[1].id).await.unwrap();
+        assert_eq!(rsvp, rsvps[1]);
+        assert_eq!(series_count(&migrated_pool).await, 1);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_series_conflict_should_rollback() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let single = abi::Reservation::new_pending(
+            "bob",
+            "meeting-room-1",
+            "2023-03-13T09:30:00-0700".parse().unwrap(),
+            "2023-03-13T10:30:00-0700".parse().unwrap(),
+            "",
+        );
+        manager.reserve(single).await.unwrap();
+
+        let err = manager
+            .reserve_series(make_weekly_series("FREQ=WEEKLY;COUNT=4"))
+            .await
+            .unwrap_err();
+        let conflicts = match err {
+            abi::Error::ConflictReservations(conflicts) => conflicts,
+            e => panic!("unexpected error: {e:?}"),
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert!(matches!(conflicts[0], ReservationConflictInfo::Parsed(_)));
+        assert_eq!(series_count(&migrated_pool).await, 0);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn cancel_occurrence_should_exclude_it() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let (series, rsvps) = manager
+            .reserve_series(make_weekly_series("FREQ=WEEKLY;COUNT=4"))
+            .await
+            .unwrap();
+        let recurrence_id = convert_to_utc_time(rsvps[1].recurrence_id.as_ref().unwrap());
+
+        let request = CancelOccurrenceRequest::new(series.id, recurrence_id)
+            .with_reason("out of office", "alice");
+        let rsvp = manager.cancel_occurrence(request.clone()).await.unwrap();
+        assert_eq!(rsvp.id, rsvps[1].id);
+        assert_eq!(rsvp.status(), ReservationStatus::Cancelled);
+        assert_eq!(rsvp.cancel_reason, "out of office");
+        assert_eq!(rsvp.cancelled_by, "alice");
+        assert!(rsvp.cancelled_at.is_some());
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+
+        let series = get_series(&migrated_pool, series.id).await;
+        assert_eq!(series.exdates, vec![rsvps[1].start.clone().unwrap()]);
+        assert_eq!(series.expand().unwrap().len(), 3);
+
+        // the same occurrence can't be cancelled twice
+        let err = manager.cancel_occurrence(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn update_series_should_replace_following_occurrences() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let (series, rsvps) = manager
+            .reserve_series(make_weekly_series("FREQ=WEEKLY;COUNT=4"))
+            .await
+            .unwrap();
+        let recurrence_id = convert_to_utc_time(rsvps[2].recurrence_id.as_ref().unwrap());
+
+        // from the third week on, meet an hour later for three more weeks
+        let new_series = ReservationSeries::new(
+            "alice",
+            "meeting-room-1",
+            "FREQ=WEEKLY;COUNT=3",
+            "America/Los_Angeles",
+            "2023-03-20T10:00:00-0700".parse().unwrap(),
+            "2023-03-20T11:00:00-0700".parse().unwrap(),
+            "weekly sync",
+        );
+        let (new_series, new_rsvps) = manager
+            .update_series(series.id, recurrence_id, new_series)
+            .await
+            .unwrap();
+        assert!(new_series.id != series.id);
+        assert_eq!(new_rsvps.len(), 3);
+        for rsvp in &rsvps[2..] {
+            let replaced = manager.get(rsvp.id).await.unwrap();
+            assert_eq!(replaced.status(), ReservationStatus::Cancelled);
+            assert_eq!(replaced.cancel_reason, "replaced by the updated series");
+        }
+        assert_eq!(manager.get(rsvps[1].id).await.unwrap(), rsvps[1]);
+
+        // the original series ends before the updated occurrence
+        let old = get_series(&migrated_pool, series.id).await;
+        assert_eq!(old.expand().unwrap().len(), 2);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn cancel_series_should_work() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let (series, rsvps) = manager
+            .reserve_series(make_weekly_series("FREQ=WEEKLY;COUNT=4"))
+            .await
+            .unwrap();
+
+        let request = CancelSeriesRequest::new(series.id).with_reason("project ended", "bob");
+        let cancelled = manager.cancel_series(request.clone()).await.unwrap();
+        assert_eq!(cancelled.len(), rsvps.len());
+        for rsvp in &cancelled {
+            assert_eq!(rsvp.status(), ReservationStatus::Cancelled);
+            assert_eq!(rsvp.cancel_reason, "project ended");
+            assert_eq!(rsvp.cancelled_by, "bob");
+            assert!(rsvp.cancelled_at.is_some());
+        }
+        // the occurrences are kept with their series, cancelled by bob
+        assert_eq!(manager.get(rsvps[0].id).await.unwrap(), cancelled[0]);
+        assert_eq!(series_count(&migrated_pool).await, 1);
+        let history = manager.history(rsvps[0].id).await.unwrap();
+        assert_eq!(history.last().unwrap().actor, "bob");
+
+        let err = manager.cancel_series(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        let err = manager
+            .cancel_series(CancelSeriesRequest::new(series.id + 1))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    fn make_weekly_series(rrule: &str) -> ReservationSeries {
+        ReservationSeries::new(
+            "alice",
+            "meeting-room-1",
+            rrule,
+            "America/Los_Angeles",
+            "2023-03-06T09:00:00-0800".parse().unwrap(),
+            "2023-03-06T10:00:00-0800".parse().unwrap(),
+            "weekly sync",
+        )
+    }
+
+    async fn get_series(pool: &PgPool, id: i64) -> ReservationSeries {
+        sqlx::query_as("SELECT * FROM rsvp.reservation_series WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn series_count(pool: &PgPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM rsvp.reservation_series")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+}
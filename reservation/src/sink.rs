@@ -0,0 +1,208 @@
+use abi::{ReservationChange, SinkConfig, SinkKind};
+use async_trait::async_trait;
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{self, AsyncWriteExt},
+    sync::Mutex,
+};
+
+use crate::{ReservationManager, Rsvp};
+
+/// destination of the reservation changes, receives every change in order
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// deliver a change, the change will be resent if an error is returned
+    async fn send(&self, change: &ReservationChange) -> Result<(), abi::Error>;
+}
+
+/// print the changes as JSON lines to stdout
+#[derive(Debug, Default)]
+pub struct StdoutSink;
+
+/// append the changes as JSON lines to a file
+#[derive(Debug)]
+pub struct FileSink {
+    file: Mutex<File>,
+}
+
+/// post each change as JSON to an http endpoint
+#[derive(Debug)]
+pub struct HttpSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl EventSink for StdoutSink {
+    async fn send(&self, change: &ReservationChange) -> Result<(), abi::Error> {
+        let line = to_json_line(change);
+        let mut stdout = io::stdout();
+        stdout.write_all(&line).await.map_err(sink_error)?;
+        stdout.flush().await.map_err(sink_error)
+    }
+}
+
+impl FileSink {
+    pub async fn open(path: &str) -> Result<Self, abi::Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(sink_error)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for FileSink {
+    async fn send(&self, change: &ReservationChange) -> Result<(), abi::Error> {
+        let line = to_json_line(change);
+        let mut file = self.file.lock().await;
+        file.write_all(&line).await.map_err(sink_error)?;
+        file.flush().await.map_err(sink_error)
+    }
+}
+
+impl HttpSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for HttpSink {
+    async fn send(&self, change: &ReservationChange) -> Result<(), abi::Error> {
+        self.client
+            .post(&self.url)
+            .json(change)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(sink_error)?;
+        Ok(())
+    }
+}
+
+/// build the event sink described by the config
+pub async fn sink_from_config(config: &SinkConfig) -> Result<Box<dyn EventSink>, abi::Error> {
+    let sink: Box<dyn EventSink> = match &config.kind {
+        SinkKind::Stdout => Box::new(StdoutSink),
+        SinkKind::File { path } => Box::new(FileSink::open(path).await?),
+        SinkKind::Http { url } => Box::new(HttpSink::new(url)),
+    };
+    Ok(sink)
+}
+
+impl ReservationManager {
+    /// feed every change to the sink, resume from the last acknowledged change of the named sink
+    pub async fn run_sink(&self, name: &str, sink: &dyn EventSink) -> Result<(), abi::Error> {
+        let group = format!("sink:{name}");
+        let request = abi::ListenRequest {
+            group: group.clone(),
+            ..Default::default()
+        };
+        let mut changes = self.listen_changes(request).await?;
+
+        while let Some(change) = changes.recv().await {
+            let change: ReservationChange = change?;
+            sink.send(&change).await?;
+            self.ack(group.clone(), change.id).await?;
+        }
+        Ok(())
+    }
+}
+
+fn to_json_line(change: &ReservationChange) -> Vec<u8> {
+    let mut line = serde_json::to_vec(change).expect("reservation change should be serializable");
+    line.push(b'\n');
+    line
+}
+
+fn sink_error(e: impl ToString) -> abi::Error {
+    abi::Error::EventSinkError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{sync::mpsc, time};
+
+    use super::*;
+    use crate::test_utils::test_manager;
+
+    /// collect the changes into a channel
+    struct ChannelSink(mpsc::UnboundedSender<ReservationChange>);
+
+    #[async_trait]
+    impl EventSink for ChannelSink {
+        async fn send(&self, change: &ReservationChange) -> Result<(), abi::Error> {
+            self.0.send(change.clone()).unwrap();
+            Ok(())
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn run_sink_should_receive_every_change() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let runner = manager.clone();
+        tokio::spawn(async move { runner.run_sink("test", &ChannelSink(tx)).await });
+
+        let rsvp = manager.reserve(make_reservation()).await.unwrap();
+        manager.change_status(rsvp.id).await.unwrap();
+
+        let created = rx.recv().await.unwrap();
+        let updated = rx.recv().await.unwrap();
+        assert_eq!(created.op, abi::RsvpUpdateType::Create);
+        assert_eq!(updated.op, abi::RsvpUpdateType::Update);
+        assert_eq!(updated.reservation_id, rsvp.id);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn file_sink_should_write_json_lines() {
+        let path = std::env::temp_dir().join(format!("rsvp-sink-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let manager = test_manager(migrated_pool.clone()).await;
+        let sink = FileSink::open(&path).await.unwrap();
+        let runner = manager.clone();
+        tokio::spawn(async move { runner.run_sink("file", &sink).await });
+
+        let rsvp = manager.reserve(make_reservation()).await.unwrap();
+        manager.delete(rsvp.id).await.unwrap();
+
+        let lines = loop {
+            let content = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            if content.lines().count() == 2 {
+                break content;
+            }
+            time::sleep(Duration::from_millis(10)).await;
+        };
+        let changes: Vec<ReservationChange> = lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(changes[0].op, abi::RsvpUpdateType::Create);
+        assert_eq!(changes[1].op, abi::RsvpUpdateType::Delete);
+        assert_eq!(changes[1].old.as_ref().unwrap().id, rsvp.id);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn make_reservation() -> abi::Reservation {
+        abi::Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "",
+        )
+    }
+}
@@ -0,0 +1,175 @@
+use abi::{
+    convert_to_timestamp, convert_to_utc_time, AvailabilityRequest, Normalizer, Reservation,
+    Resource, ResourceSearch, Suggestion, MAX_SEARCH_LIMIT,
+};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{search::search_candidates, ReservationManager};
+
+/// how far before and after the requested window to look for free windows on the same resource
+const SEARCH_HOURS: i64 = 24;
+
+impl ReservationManager {
+    /// free windows with the same duration as the reservation: the nearest ones on the same
+    /// resource first, then the same window on similar resources (same type and tags)
+    pub async fn suggest(
+        &self,
+        rsvp: &Reservation,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>, abi::Error> {
+        let (Some(start), Some(end)) = (rsvp.start.as_ref(), rsvp.end.as_ref()) else {
+            return Ok(vec![]);
+        };
+        let start = convert_to_utc_time(start);
+        let end = convert_to_utc_time(end);
+        let resource = self.get_resource(&rsvp.resource_id).await?;
+
+        let same = if resource.retired {
+            vec![]
+        } else {
+            self.nearest_windows(&resource, start, end).await?
+        };
+
+        // a resource without type or tags has nothing to be similar to
+        let mut similar = vec![];
+        if !resource.resource_type.is_empty() || !resource.tags.is_empty() {
+            let mut search = ResourceSearch {
+                tags: resource.tags.clone(),
+                resource_type: resource.resource_type.clone(),
+                start: rsvp.start.clone(),
+                end: rsvp.end.clone(),
+                // one more for the resource itself, but no more than a search could return
+                limit: limit.saturating_add(1).min(MAX_SEARCH_LIMIT as usize) as i32,
+                ..Default::default()
+            };
+            search.normalize()?;
+            let mut conn = self.pool.acquire().await?;
+            similar = search_candidates(&mut conn, &search)
+                .await?
+                .into_iter()
+                .filter_map(|c| c.resource)
+                .filter(|r| r.id != resource.id)
+                .map(|r| make_suggestion(&r.id, start, end))
+                .collect();
+        }
+
+        // share the suggestions between the same resource and the similar ones
+        let same_count = same
+            .len()
+            .min(limit.saturating_sub(similar.len().min(limit / 2)));
+        let mut suggestions: Vec<_> = same.into_iter().take(same_count).collect();
+        suggestions.extend(similar.into_iter().take(limit - same_count));
+        Ok(suggestions)
+    }
+
+    /// free windows on the resource with the same duration, the nearest first
+    async fn nearest_windows(
+        &self,
+        resource: &Resource,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Suggestion>, abi::Error> {
+        let duration = end - start;
+        let request = AvailabilityRequest::new(
+            &resource.id,
+            start - Duration::hours(SEARCH_HOURS),
+            end + Duration::hours(SEARCH_HOURS),
+            duration,
+            None,
+        );
+        let reserved = self
+            .reserved_windows(resource, request.get_timespan())
+            .await?;
+
+        // the start in each free slot closest to the requested one
+        let mut starts: Vec<_> = request
+            .free_slots(&reserved, resource.capacity as usize)
+            .iter()
+            .map(|slot| {
+                let from = convert_to_utc_time(slot.start.as_ref().unwrap());
+                let to = convert_to_utc_time(slot.end.as_ref().unwrap()) - duration;
+                start.clamp(from, to)
+            })
+            .collect();
+        starts.sort_by_key(|s| ((*s - start).num_seconds().abs(), *s));
+
+        Ok(starts
+            .into_iter()
+            .map(|s| make_suggestion(&resource.id, s, s + duration))
+            .collect())
+    }
+}
+
+fn make_suggestion(rid: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Suggestion {
+    Suggestion {
+        resource_id: rid.into(),
+        start: Some(convert_to_timestamp(&start)),
+        end: Some(convert_to_timestamp(&end)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn suggest_should_return_nearest_windows_and_similar_resources() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        for id in ["studio-a", "studio-b", "studio-c"] {
+            let mut resource = Resource::new(id, id, "studio");
+            resource.tags = vec![if id == "studio-c" { "quiet" } else { "mixer" }.into()];
+            manager.create_resource(resource).await.unwrap();
+        }
+        // studio-a is taken 13:00 to 15:20, and from 16:00 on
+        manager
+            .reserve(make_reservation("studio-a", "13:00", "15:20"))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_reservation("studio-a", "16:00", "23:00"))
+            .await
+            .unwrap();
+
+        let rsvp = make_reservation("studio-a", "14:00", "15:00");
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        let suggestions = manager.suggest(&rsvp, 3).await.unwrap();
+        let found: Vec<_> = suggestions
+            .iter()
+            .map(|s| {
+                (
+                    s.resource_id.as_str(),
+                    convert_to_utc_time(s.start.as_ref().unwrap()),
+                )
+            })
+            .collect();
+        // the 40 minutes gap at 15:20 is too short, studio-c is not similar as it has no mixer.
+        // The windows on studio-a keep a microsecond off the reservations they are next to
+        let us = Duration::microseconds(1);
+        assert_eq!(
+            found,
+            vec![
+                ("studio-a", t("12:00") - us),
+                ("studio-a", t("23:00") + us),
+                ("studio-b", t("14:00")),
+            ]
+        );
+    }
+
+    fn t(s: &str) -> DateTime<Utc> {
+        format!("2023-01-25T{s}:00-0700").parse().unwrap()
+    }
+
+    fn make_reservation(rid: &str, start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(
+            "alice",
+            rid,
+            format!("2023-01-25T{start}:00-0700").parse().unwrap(),
+            format!("2023-01-25T{end}:00-0700").parse().unwrap(),
+            "",
+        )
+    }
+}
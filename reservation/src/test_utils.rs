@@ -0,0 +1,23 @@
+use sqlx::PgPool;
+
+use crate::ReservationManager;
+
+/// resources reserved by the tests
+const TEST_RESOURCES: [&str; 6] = [
+    "ixia-test-1",
+    "ixia-test-2",
+    "meeting-room-1",
+    "mountain-view-room-417",
+    "ocean-view-room-417",
+    "ocean-view-room-713",
+];
+
+/// manager on the migrated database, with the test resources created
+pub(crate) async fn test_manager(pool: PgPool) -> ReservationManager {
+    sqlx::query("INSERT INTO rsvp.resources (id, name) SELECT id, id FROM unnest($1::text[]) AS id ON CONFLICT DO NOTHING")
+        .bind(&TEST_RESOURCES[..])
+        .execute(&pool)
+        .await
+        .unwrap();
+    ReservationManager::new(pool)
+}
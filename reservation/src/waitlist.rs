@@ -0,0 +1,203 @@
+use abi::{ListWaitlistRequest, Reservation, Validator, WaitlistEntry};
+
+use crate::{resource::check_resource, ReservationManager};
+
+impl ReservationManager {
+    /// wait for the window of the reservation, it's reserved as soon as the window is free.
+    /// That might be right away if the window was freed after the reservation conflicted
+    pub async fn join_waitlist(&self, rsvp: Reservation) -> Result<WaitlistEntry, abi::Error> {
+        rsvp.validate_new()?;
+        let mut tx = self.begin().await?;
+        check_resource(&mut tx, &rsvp.resource_id).await?;
+
+        let timespan = rsvp.get_timespan();
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO rsvp.waitlist (user_id, resource_id, timespan, note) VALUES ($1, $2, $3, $4) RETURNING id",
+        )
+        .bind(&rsvp.user_id)
+        .bind(&rsvp.resource_id)
+        .bind(&timespan)
+        .bind(&rsvp.note)
+        .fetch_one(&mut tx)
+        .await?;
+        sqlx::query("SELECT rsvp.promote_waitlist($1, $2)")
+            .bind(&rsvp.resource_id)
+            .bind(timespan)
+            .execute(&mut tx)
+            .await?;
+        let entry = sqlx::query_as("SELECT * FROM rsvp.waitlist WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut tx)
+            .await?;
+        tx.commit().await?;
+        Ok(entry)
+    }
+
+    /// entries still waiting, in the order they joined
+    pub async fn list_waitlist(
+        &self,
+        request: ListWaitlistRequest,
+    ) -> Result<Vec<WaitlistEntry>, abi::Error> {
+        let entries = sqlx::query_as(
+            "SELECT * FROM rsvp.waitlist WHERE reservation_id IS NULL AND ($1 = '' OR resource_id = $1) ORDER BY id",
+        )
+        .bind(&request.resource_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(entries)
+    }
+
+    /// leave the waitlist, the promoted entries can't leave but cancel their reservations
+    pub async fn leave_waitlist(&self, id: i64) -> Result<WaitlistEntry, abi::Error> {
+        id.validate()?;
+        let entry = sqlx::query_as(
+            "DELETE FROM rsvp.waitlist WHERE id = $1 AND reservation_id IS NULL RETURNING *",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::{convert_to_timestamp, CancelRequest, RescheduleRequest};
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::test_utils::test_manager;
+    use crate::Rsvp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn waitlist_should_promote_the_first_entry_fitting_the_freed_window() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let alice = manager
+            .reserve(make_reservation("alice", "09:00", "10:00"))
+            .await
+            .unwrap();
+
+        let bob = make_reservation("bob", "09:30", "10:30");
+        let err = manager.reserve(bob.clone()).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        let bob = manager.join_waitlist(bob).await.unwrap();
+        assert_eq!(bob.reservation_id, 0);
+        let carol = manager
+            .join_waitlist(make_reservation("carol", "09:00", "09:45"))
+            .await
+            .unwrap();
+        let waiting = manager
+            .list_waitlist(ListWaitlistRequest::new("meeting-room-1"))
+            .await
+            .unwrap();
+        assert_eq!(waiting, vec![bob.clone(), carol.clone()]);
+
+        // bob joined first, carol still conflicts with him
+        manager
+            .acting_as("alice")
+            .cancel(CancelRequest::new(alice.id))
+            .await
+            .unwrap();
+        let rid = promoted(&migrated_pool, bob.id).await.unwrap();
+        let rsvp = manager.get(rid).await.unwrap();
+        assert_eq!(rsvp.user_id, "bob");
+        assert_eq!(rsvp.status(), abi::ReservationStatus::Pending);
+        assert_eq!((rsvp.start, rsvp.end), (bob.start, bob.end));
+        assert_eq!(rsvp.waitlist_id, bob.id);
+        // the promotion is made by the waitlist, not by alice cancelling hers
+        let history = manager.history(rid).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].actor, "waitlist");
+        assert_eq!(manager.history(alice.id).await.unwrap()[1].actor, "alice");
+        let created: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM rsvp.reservation_changes WHERE reservation_id = $1 AND op = 'create'",
+        )
+        .bind(rid)
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+        assert_eq!(created, 1);
+        assert_eq!(promoted(&migrated_pool, carol.id).await, None);
+
+        // moving bob away frees the window for carol
+        let request = RescheduleRequest::new(
+            rid,
+            convert_to_timestamp(&t("11:00")),
+            convert_to_timestamp(&t("12:00")),
+        );
+        manager.reschedule(request).await.unwrap();
+        let rid = promoted(&migrated_pool, carol.id).await.unwrap();
+        assert_eq!(manager.get(rid).await.unwrap().user_id, "carol");
+        let waiting = manager
+            .list_waitlist(ListWaitlistRequest::new(""))
+            .await
+            .unwrap();
+        assert!(waiting.is_empty());
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn waitlist_should_skip_the_entries_failing_to_promote() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let alice = manager
+            .reserve(make_reservation("alice", "09:00", "10:00"))
+            .await
+            .unwrap();
+        let mallory = manager
+            .join_waitlist(make_reservation("mallory", "09:00", "10:00"))
+            .await
+            .unwrap();
+        let bob = manager
+            .join_waitlist(make_reservation("bob", "09:00", "10:00"))
+            .await
+            .unwrap();
+
+        // mallory's reservation fails on something other than a conflict
+        sqlx::query(
+            "ALTER TABLE rsvp.reservations ADD CONSTRAINT no_mallory CHECK (user_id <> 'mallory')",
+        )
+        .execute(&migrated_pool)
+        .await
+        .unwrap();
+        manager.cancel(CancelRequest::new(alice.id)).await.unwrap();
+        assert_eq!(promoted(&migrated_pool, mallory.id).await, None);
+        let rid = promoted(&migrated_pool, bob.id).await.unwrap();
+        assert_eq!(manager.get(rid).await.unwrap().user_id, "bob");
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn join_waitlist_should_reserve_right_away_if_the_window_is_free() {
+        let manager = test_manager(migrated_pool.clone()).await;
+        let entry = manager
+            .join_waitlist(make_reservation("alice", "09:00", "10:00"))
+            .await
+            .unwrap();
+        assert_ne!(entry.reservation_id, 0);
+        let err = manager.leave_waitlist(entry.id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        let entry = manager
+            .join_waitlist(make_reservation("bob", "09:00", "10:00"))
+            .await
+            .unwrap();
+        assert_eq!(entry.reservation_id, 0);
+        assert_eq!(manager.leave_waitlist(entry.id).await.unwrap(), entry);
+        let err = manager.join_waitlist(make_reservation("bob", "10:00", "09:00"));
+        assert_eq!(err.await.unwrap_err(), abi::Error::InvalidTime);
+    }
+
+    async fn promoted(pool: &sqlx::PgPool, id: i64) -> Option<i64> {
+        sqlx::query_scalar("SELECT reservation_id FROM rsvp.waitlist WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    fn t(s: &str) -> DateTime<Utc> {
+        format!("2023-01-25T{s}:00-0700").parse().unwrap()
+    }
+
+    fn make_reservation(uid: &str, start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(uid, "meeting-room-1", t(start).into(), t(end).into(), "")
+    }
+}
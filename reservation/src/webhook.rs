@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use abi::{ReservationChange, WebhookConfig};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::time;
+use tracing::log::warn;
+
+use crate::{ReservationManager, Rsvp};
+
+/// header carrying the HMAC-SHA256 signature of the payload, e.g. "sha256=<hex digest>"
+pub const SIGNATURE_HEADER: &str = "x-reservation-signature";
+/// header carrying the id of the change
+pub const CHANGE_ID_HEADER: &str = "x-reservation-change-id";
+
+/// posts the reservation changes to a webhook as JSON, in order
+pub struct WebhookDispatcher {
+    manager: ReservationManager,
+    config: WebhookConfig,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(manager: ReservationManager, config: WebhookConfig) -> Self {
+        Self {
+            manager,
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// consumer group tracking the delivery offset of the webhook
+    fn group(&self) -> String {
+        format!("webhook:{}", self.config.name)
+    }
+
+    /// dispatch the changes until the change queue fails
+    pub async fn run(&self) -> Result<(), abi::Error> {
+        let request = abi::ListenRequest {
+            group: self.group(),
+            ..Default::default()
+        };
+        let mut changes = self.manager.listen_changes(request).await?;
+
+        // a delivery outlasting the ack timeout has the group rewound, so the changes already
+        // acked are sent again
+        let mut acked = 0;
+        while let Some(change) = changes.recv().await {
+            let change: ReservationChange = change?;
+            if change.id <= acked {
+                continue;
+            }
+            self.dispatch(&change).await?;
+            self.manager.ack(self.group(), change.id).await?;
+            acked = change.id;
+        }
+        Ok(())
+    }
+
+    /// deliver the change with exponential backoff, move it to the dead letters if all attempts failed
+    async fn dispatch(&self, change: &ReservationChange) -> Result<(), abi::Error> {
+        let payload =
+            serde_json::to_vec(change).expect("reservation change should be serializable");
+        let signature = sign(&self.config.secret, &payload);
+
+        let max_backoff = Duration::from_millis(self.config.max_backoff);
+        let mut backoff = Duration::from_millis(self.config.backoff).min(max_backoff);
+        let mut attempts = 1;
+        loop {
+            let err = match self.post(change.id, &payload, &signature).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+            warn!(
+                "Webhook {} failed to deliver change {} (attempt {attempts}): {err}",
+                self.config.name, change.id
+            );
+            if attempts >= self.config.max_attempts {
+                return self.dead_letter(change, &payload, &err, attempts).await;
+            }
+            time::sleep(backoff).await;
+            backoff = next_backoff(backoff, max_backoff);
+            attempts += 1;
+        }
+    }
+
+    async fn post(&self, id: i64, payload: &[u8], signature: &str) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(CHANGE_ID_HEADER, id)
+            .body(payload.to_vec())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("unexpected status {}", response.status()))
+        }
+    }
+
+    async fn dead_letter(
+        &self,
+        change: &ReservationChange,
+        payload: &[u8],
+        err: &str,
+        attempts: u32,
+    ) -> Result<(), abi::Error> {
+        sqlx::query(
+            "INSERT INTO rsvp.webhook_dead_letters (webhook, url, change_id, payload, error, attempts) VALUES ($1, $2, $3, $4::jsonb, $5, $6)",
+        )
+        .bind(&self.config.name)
+        .bind(&self.config.url)
+        .bind(change.id)
+        .bind(String::from_utf8_lossy(payload))
+        .bind(err)
+        .bind(attempts as i32)
+        .execute(&self.manager.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// double the backoff, up to the max
+fn next_backoff(backoff: Duration, max: Duration) -> Duration {
+    backoff.saturating_mul(2).min(max)
+}
+
+/// sign the payload with HMAC-SHA256, receivers should verify it with the shared secret
+pub fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use hyper::{
+        body::Bytes,
+        service::{make_service_fn, service_fn},
+        Body, HeaderMap, Request, Response, Server, StatusCode,
+    };
+    use sqlx::PgPool;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::test_utils::test_manager;
+
+    #[test]
+    fn sign_should_work() {
+        assert_eq!(
+            sign("secret", b"hello"),
+            "sha256=88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b"
+        );
+    }
+
+    #[test]
+    fn backoff_should_be_capped() {
+        let max = Duration::from_secs(30);
+        assert_eq!(
+            next_backoff(Duration::from_secs(1), max),
+            Duration::from_secs(2)
+        );
+        assert_eq!(next_backoff(Duration::from_secs(20), max), max);
+        assert_eq!(next_backoff(Duration::MAX, max), max);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn webhook_should_post_signed_changes() {
+        let (url, mut requests) = start_stand_in(StatusCode::OK).await;
+        let manager = test_manager(migrated_pool.clone()).await;
+        let dispatcher = WebhookDispatcher::new(manager.clone(), webhook_config(url));
+        tokio::spawn(async move { dispatcher.run().await });
+
+        let rsvp = manager.reserve(make_reservation()).await.unwrap();
+
+        let (headers, body) = requests.recv().await.unwrap();
+        assert_eq!(headers[SIGNATURE_HEADER], sign("secret", &body));
+        let change: ReservationChange = serde_json::from_slice(&body).unwrap();
+        assert_eq!(headers[CHANGE_ID_HEADER], change.id.to_string());
+        assert_eq!(change.op, abi::RsvpUpdateType::Create);
+        assert_eq!(change.new.unwrap().id, rsvp.id);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn webhook_should_not_repeat_changes_retried_past_ack_timeout() {
+        let (url, mut requests) = start_stand_in(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let manager = test_manager(migrated_pool.clone())
+            .await
+            .with_ack_timeout(Duration::from_millis(50));
+        let config = WebhookConfig {
+            backoff: 100,
+            ..webhook_config(url)
+        };
+        let dispatcher = WebhookDispatcher::new(manager.clone(), config);
+        tokio::spawn(async move { dispatcher.run().await });
+
+        manager.reserve(make_reservation()).await.unwrap();
+
+        // the retries take longer than the ack timeout, but the change isn't retried again after
+        for _ in 0..3 {
+            requests.recv().await.unwrap();
+        }
+        wait_for_dead_letter(&migrated_pool).await;
+        time::sleep(Duration::from_millis(500)).await;
+        assert!(requests.try_recv().is_err());
+        let dead_letters: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM rsvp.webhook_dead_letters")
+                .fetch_one(&migrated_pool)
+                .await
+                .unwrap();
+        assert_eq!(dead_letters, 1);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn webhook_should_dead_letter_failed_changes() {
+        let (url, mut requests) = start_stand_in(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let manager = test_manager(migrated_pool.clone()).await;
+        let dispatcher = WebhookDispatcher::new(manager.clone(), webhook_config(url));
+        tokio::spawn(async move { dispatcher.run().await });
+
+        manager.reserve(make_reservation()).await.unwrap();
+
+        // retried until max attempts
+        for _ in 0..3 {
+            requests.recv().await.unwrap();
+        }
+        let (attempts, error) = wait_for_dead_letter(&migrated_pool).await;
+        assert_eq!(attempts, 3);
+        assert_eq!(error, "unexpected status 500 Internal Server Error");
+    }
+
+    fn webhook_config(url: String) -> WebhookConfig {
+        WebhookConfig {
+            name: "billing".into(),
+            url,
+            secret: "secret".into(),
+            max_attempts: 3,
+            backoff: 10,
+            max_backoff: 1_000,
+        }
+    }
+
+    fn make_reservation() -> abi::Reservation {
+        abi::Reservation::new_pending(
+            "alice",
+            "ixia-test-1",
+            "2023-01-25T15:00:00-0700".parse().unwrap(),
+            "2023-02-25T12:00:00-0700".parse().unwrap(),
+            "",
+        )
+    }
+
+    async fn wait_for_dead_letter(pool: &PgPool) -> (i32, String) {
+        loop {
+            let row = sqlx::query_as("SELECT attempts, error FROM rsvp.webhook_dead_letters")
+                .fetch_optional(pool)
+                .await
+                .unwrap();
+            if let Some(row) = row {
+                return row;
+            }
+            time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    // local http server recording the requests it receives
+    async fn start_stand_in(
+        status: StatusCode,
+    ) -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let make_svc = make_service_fn(move |_| {
+            let tx = tx.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let tx = tx.clone();
+                    async move {
+                        let headers = req.headers().clone();
+                        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                        let _ = tx.send((headers, body));
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Body::empty())
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_svc);
+        let url = format!("http://{}/hooks/reservations", server.local_addr());
+        tokio::spawn(server);
+        (url, rx)
+    }
+}
@@ -1,15 +1,24 @@
 use std::pin::Pin;
 
-use abi::{reservation_service_server::ReservationServiceServer, Config, Reservation};
+use abi::{
+    reservation_service_server::ReservationServiceServer, Config, ListenResponse, LiveQueryEvent,
+    Reservation,
+};
 use futures::Stream;
-use reservation::ReservationManager;
+use reservation::{ReservationManager, WebhookDispatcher};
 use tokio::sync::mpsc;
 use tonic::{transport::Server, Status};
 
+mod retention;
 mod service;
+mod sink;
+mod webhook;
 
 #[cfg(test)]
 pub mod test_utils;
+
+/// metadata key of who makes the request, recorded in the reservation history
+pub const ACTOR_HEADER: &str = "x-actor";
 pub struct RsvpService {
     manager: ReservationManager,
 }
@@ -19,6 +28,8 @@ pub struct TonicReceiverStream<T> {
 }
 
 type ReservationStream = Pin<Box<dyn Stream<Item = Result<Reservation, Status>> + Send>>;
+type ListenResponseStream = Pin<Box<dyn Stream<Item = Result<ListenResponse, Status>> + Send>>;
+type LiveQueryStream = Pin<Box<dyn Stream<Item = Result<LiveQueryEvent, Status>> + Send>>;
 
 pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     // dbg!(config);
@@ -26,6 +37,17 @@ pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     println!("Listening on {addr}");
 
     let svc = RsvpService::from_config(config).await?;
+    if config.changes.retention.is_enabled() {
+        let policy = config.changes.retention.clone();
+        tokio::spawn(retention::run_retention(svc.manager.clone(), policy));
+    }
+    for webhook in &config.webhooks {
+        let dispatcher = WebhookDispatcher::new(svc.manager.clone(), webhook.clone());
+        tokio::spawn(webhook::run_webhook(dispatcher));
+    }
+    for sink in &config.sinks {
+        tokio::spawn(sink::run_sink(svc.manager.clone(), sink.clone()));
+    }
     let svc = ReservationServiceServer::new(svc);
 
     Server::builder().add_service(svc).serve(addr).await?;
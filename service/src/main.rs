@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use abi::Config;
-use anyhow::{Ok, Result};
+use anyhow::Result;
 use reservation_service::start_server;
 
 #[tokio::main]
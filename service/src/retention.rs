@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use abi::RetentionConfig;
+use reservation::ReservationManager;
+use tokio::time;
+use tracing::{info, warn};
+
+/// periodically drop the changes out of the retention policy
+pub async fn run_retention(manager: ReservationManager, policy: RetentionConfig) {
+    let mut interval = time::interval(Duration::from_secs(policy.interval));
+    loop {
+        interval.tick().await;
+        match manager.apply_retention(&policy).await {
+            Ok(0) => {}
+            Ok(dropped) => info!("Retention dropped {dropped} reservation changes"),
+            Err(e) => warn!("Retention failed: {e:?}"),
+        }
+    }
+}
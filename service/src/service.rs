@@ -1,23 +1,46 @@
-use std::task::Poll;
+use std::{task::Poll, time::Duration};
 
 use abi::{
-    reservation_service_server::ReservationService, CancelRequest, CancelResponse, ConfirmRequest,
-    ConfirmResponse, FilterRequest, FilterResponse, GetRequest, GetResponse, ListenRequest,
-    QueryRequest, ReservationId, ReserveRequest, ReserveResponse, UpdateRequest, UpdateResponse,
+    convert_to_utc_time, reservation_service_server::ReservationService, AckRequest, AckResponse,
+    AvailabilityRequest, AvailabilityResponse, BlockRequest, BlockResponse,
+    CancelOccurrenceRequest, CancelOccurrenceResponse, CancelRequest, CancelResponse,
+    CancelSeriesRequest, CancelSeriesResponse, ConfirmRequest, ConfirmResponse,
+    CreateResourceRequest, CreateResourceResponse, DeleteResourceRequest, DeleteResourceResponse,
+    FilterRequest, FilterResponse, FindAvailableRequest, FindAvailableResponse, GetRequest,
+    GetResourceRequest, GetResourceResponse, GetResponse, HistoryRequest, HistoryResponse,
+    LeaveWaitlistRequest, LeaveWaitlistResponse, LiftBlockRequest, LiftBlockResponse,
+    ListBlocksRequest, ListBlocksResponse, ListResourcesRequest, ListResourcesResponse,
+    ListWaitlistRequest, ListWaitlistResponse, ListenRequest, QueryRequest, RescheduleRequest,
+    RescheduleResponse, ReserveBatchRequest, ReserveBatchResponse, ReserveRequest, ReserveResponse,
+    ReserveSeriesRequest, ReserveSeriesResponse, RestoreRequest, RestoreResponse, UpdateRequest,
+    UpdateResourceRequest, UpdateResourceResponse, UpdateResponse, UpdateSeriesRequest,
+    UpdateSeriesResponse, Validator,
 };
 use futures::Stream;
 use reservation::{ReservationManager, Rsvp};
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::ReceiverStream;
 use tonic::{async_trait, Request, Response, Status};
 
-use crate::{ReservationStream, RsvpService, TonicReceiverStream};
+use crate::{
+    ListenResponseStream, LiveQueryStream, ReservationStream, RsvpService, TonicReceiverStream,
+    ACTOR_HEADER,
+};
 
 impl RsvpService {
     pub async fn from_config(config: &abi::Config) -> Result<Self, anyhow::Error> {
-        Ok(Self {
-            manager: ReservationManager::from_config(&config.db).await?,
-        })
+        let manager = ReservationManager::from_config(&config.db)
+            .await?
+            .with_ack_timeout(Duration::from_secs(config.changes.ack_timeout))
+            .with_listen_buffer(config.changes.listen_buffer);
+        Ok(Self { manager })
+    }
+
+    /// manager acting as the one in the request metadata, so the changes are recorded as theirs
+    fn manager_for<T>(&self, request: &Request<T>) -> ReservationManager {
+        match request.metadata().get(ACTOR_HEADER).map(|v| v.to_str()) {
+            Some(Ok(actor)) if !actor.is_empty() => self.manager.acting_as(actor),
+            _ => self.manager.clone(),
+        }
     }
 }
 
@@ -28,14 +51,43 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<ReserveRequest>,
     ) -> Result<Response<ReserveResponse>, Status> {
+        let manager = self.manager_for(&request);
         let request = request.into_inner();
         if request.reservation.is_none() {
             return Err(Status::invalid_argument("missing reservation"));
         }
-        let reservation = self.manager.reserve(request.reservation.unwrap()).await?;
-        Ok(Response::new(ReserveResponse {
-            reservation: Some(reservation),
-        }))
+        request.validate()?;
+        let rsvp = request.reservation.unwrap();
+        match manager.reserve(rsvp.clone()).await {
+            Ok(reservation) => Ok(Response::new(ReserveResponse {
+                reservation: Some(reservation),
+                waitlisted: None,
+            })),
+            Err(abi::Error::ConflictReservation(_)) if request.waitlist => {
+                let entry = manager.join_waitlist(rsvp).await?;
+                Ok(Response::new(ReserveResponse {
+                    reservation: None,
+                    waitlisted: Some(entry),
+                }))
+            }
+            Err(abi::Error::ConflictReservation(info)) if request.suggestions > 0 => {
+                let limit = request.suggestions as usize;
+                let suggestions = manager.suggest(&rsvp, limit).await?;
+                Err(info.into_status(suggestions))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// make several reservations in one transaction
+    async fn reserve_batch(
+        &self,
+        request: Request<ReserveBatchRequest>,
+    ) -> Result<Response<ReserveBatchResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        let reservations = manager.reserve_many(request.reservations).await?;
+        Ok(Response::new(ReserveBatchResponse { reservations }))
     }
 
     /// confirm a pending reservation, if reservation is not pending, do nothing
@@ -43,36 +95,178 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<ConfirmRequest>,
     ) -> Result<Response<ConfirmResponse>, Status> {
+        let manager = self.manager_for(&request);
         let request = request.into_inner();
-        let reservation = self.manager.change_status(request.id).await?;
+        let reservation = manager.change_status(request.id).await?;
         Ok(Response::new(ConfirmResponse {
             reservation: Some(reservation),
         }))
     }
 
-    /// update the reservation note
+    /// update the reservation fields listed in the field mask
     async fn update(
         &self,
         request: Request<UpdateRequest>,
     ) -> Result<Response<UpdateResponse>, Status> {
+        let manager = self.manager_for(&request);
         let request = request.into_inner();
-        let reservation = self.manager.update_note(request.id, request.note).await?;
+        let reservation = manager.update(request).await?;
         Ok(Response::new(UpdateResponse {
             reservation: Some(reservation),
         }))
     }
 
+    /// move a reservation to another time window
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> Result<Response<RescheduleResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        let reservation = manager.reschedule(request).await?;
+        Ok(Response::new(RescheduleResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
     /// cancel a reservation
     async fn cancel(
         &self,
         request: Request<CancelRequest>,
     ) -> Result<Response<CancelResponse>, Status> {
+        let manager = self.manager_for(&request);
         let request = request.into_inner();
-        let reservation = self.manager.delete(request.id).await?;
+        let reservation = manager.cancel(request).await?;
         Ok(Response::new(CancelResponse {
             reservation: Some(reservation),
         }))
     }
+
+    /// restore a cancelled reservation if its window is still free
+    async fn restore(
+        &self,
+        request: Request<RestoreRequest>,
+    ) -> Result<Response<RestoreResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        let reservation = manager.restore(request.id).await?;
+        Ok(Response::new(RestoreResponse {
+            reservation: Some(reservation),
+        }))
+    }
+    /// block a resource for maintenance
+    async fn block(
+        &self,
+        request: Request<BlockRequest>,
+    ) -> Result<Response<BlockResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        Ok(Response::new(manager.block(request).await?))
+    }
+    /// list the blocks of the resources
+    async fn list_blocks(
+        &self,
+        request: Request<ListBlocksRequest>,
+    ) -> Result<Response<ListBlocksResponse>, Status> {
+        let request = request.into_inner();
+        let blocks = self.manager.list_blocks(request).await?;
+        Ok(Response::new(ListBlocksResponse { blocks }))
+    }
+    /// lift a block
+    async fn lift_block(
+        &self,
+        request: Request<LiftBlockRequest>,
+    ) -> Result<Response<LiftBlockResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        let block = manager.lift_block(request.id).await?;
+        Ok(Response::new(LiftBlockResponse { block: Some(block) }))
+    }
+    /// list the entries waiting for their windows
+    async fn list_waitlist(
+        &self,
+        request: Request<ListWaitlistRequest>,
+    ) -> Result<Response<ListWaitlistResponse>, Status> {
+        let request = request.into_inner();
+        let entries = self.manager.list_waitlist(request).await?;
+        Ok(Response::new(ListWaitlistResponse { entries }))
+    }
+    /// leave the waitlist
+    async fn leave_waitlist(
+        &self,
+        request: Request<LeaveWaitlistRequest>,
+    ) -> Result<Response<LeaveWaitlistResponse>, Status> {
+        let request = request.into_inner();
+        let entry = self.manager.leave_waitlist(request.id).await?;
+        Ok(Response::new(LeaveWaitlistResponse { entry: Some(entry) }))
+    }
+
+    /// reserve all the occurrences of a recurring reservation
+    async fn reserve_series(
+        &self,
+        request: Request<ReserveSeriesRequest>,
+    ) -> Result<Response<ReserveSeriesResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        if request.series.is_none() {
+            return Err(Status::invalid_argument("missing series"));
+        }
+        let (series, reservations) = manager.reserve_series(request.series.unwrap()).await?;
+        Ok(Response::new(ReserveSeriesResponse {
+            series: Some(series),
+            reservations,
+        }))
+    }
+
+    /// cancel a single occurrence of a series
+    async fn cancel_occurrence(
+        &self,
+        request: Request<CancelOccurrenceRequest>,
+    ) -> Result<Response<CancelOccurrenceResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        if request.recurrence_id.is_none() {
+            return Err(Status::invalid_argument("missing recurrence id"));
+        }
+        let reservation = manager.cancel_occurrence(request).await?;
+        Ok(Response::new(CancelOccurrenceResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    /// replace an occurrence and all the following ones with a new series
+    async fn update_series(
+        &self,
+        request: Request<UpdateSeriesRequest>,
+    ) -> Result<Response<UpdateSeriesResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        if request.recurrence_id.is_none() {
+            return Err(Status::invalid_argument("missing recurrence id"));
+        }
+        if request.series.is_none() {
+            return Err(Status::invalid_argument("missing series"));
+        }
+        let recurrence_id = convert_to_utc_time(request.recurrence_id.as_ref().unwrap());
+        let (series, reservations) = manager
+            .update_series(request.series_id, recurrence_id, request.series.unwrap())
+            .await?;
+        Ok(Response::new(UpdateSeriesResponse {
+            series: Some(series),
+            reservations,
+        }))
+    }
+
+    /// cancel all the occurrences of a series
+    async fn cancel_series(
+        &self,
+        request: Request<CancelSeriesRequest>,
+    ) -> Result<Response<CancelSeriesResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        let reservations = manager.cancel_series(request).await?;
+        Ok(Response::new(CancelSeriesResponse { reservations }))
+    }
     /// get a reservation by id
     async fn get(&self, request: Request<GetRequest>) -> Result<Response<GetResponse>, Status> {
         let request = request.into_inner();
@@ -115,13 +309,130 @@ impl ReservationService for RsvpService {
         }))
     }
     /// Server streaming response type for the listen method.
-    type listenStream = ReservationStream;
+    type listenStream = ListenResponseStream;
     /// another system could monitor newly added/confirmed/canceled reservations
     async fn listen(
         &self,
         request: Request<ListenRequest>,
     ) -> Result<Response<Self::listenStream>, Status> {
-        todo!()
+        let request = request.into_inner();
+        let changes = self.manager.listen(request).await?;
+        let stream = TonicReceiverStream::new(changes);
+        Ok(Response::new(Box::pin(stream)))
+    }
+    /// acknowledge the changes processed by a consumer group
+    async fn ack(&self, request: Request<AckRequest>) -> Result<Response<AckResponse>, Status> {
+        let request = request.into_inner();
+        let offset = self.manager.ack(request.group, request.id).await?;
+        Ok(Response::new(AckResponse { offset }))
+    }
+    /// who changed the reservation and when
+    async fn history(
+        &self,
+        request: Request<HistoryRequest>,
+    ) -> Result<Response<HistoryResponse>, Status> {
+        let request = request.into_inner();
+        let entries = self.manager.history(request.id).await?;
+        Ok(Response::new(HistoryResponse { entries }))
+    }
+    /// Server streaming response type for the live_query method.
+    type live_queryStream = LiveQueryStream;
+    /// query reservations, then keep sending the deltas of the result set
+    async fn live_query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<Self::live_queryStream>, Status> {
+        let request = request.into_inner();
+        if request.query.is_none() {
+            return Err(Status::invalid_argument("missing query params"));
+        }
+        let events = self.manager.live_query(request.query.unwrap()).await?;
+        let stream = TonicReceiverStream::new(events);
+        Ok(Response::new(Box::pin(stream)))
+    }
+    /// add a resource which could be reserved
+    async fn create_resource(
+        &self,
+        request: Request<CreateResourceRequest>,
+    ) -> Result<Response<CreateResourceResponse>, Status> {
+        let request = request.into_inner();
+        if request.resource.is_none() {
+            return Err(Status::invalid_argument("missing resource"));
+        }
+        let resource = self
+            .manager
+            .create_resource(request.resource.unwrap())
+            .await?;
+        Ok(Response::new(CreateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// get a resource by id
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> Result<Response<GetResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.get_resource(&request.id).await?;
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// update the resource fields listed in the field mask
+    async fn update_resource(
+        &self,
+        request: Request<UpdateResourceRequest>,
+    ) -> Result<Response<UpdateResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.update_resource(request).await?;
+        Ok(Response::new(UpdateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// delete a resource without reservations
+    async fn delete_resource(
+        &self,
+        request: Request<DeleteResourceRequest>,
+    ) -> Result<Response<DeleteResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.delete_resource(&request.id).await?;
+        Ok(Response::new(DeleteResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// list resources, order by resource id
+    async fn list_resources(
+        &self,
+        request: Request<ListResourcesRequest>,
+    ) -> Result<Response<ListResourcesResponse>, Status> {
+        let request = request.into_inner();
+        let resources = self.manager.list_resources(request.include_retired).await?;
+        Ok(Response::new(ListResourcesResponse { resources }))
+    }
+    /// find the free slots of a resource
+    async fn availability(
+        &self,
+        request: Request<AvailabilityRequest>,
+    ) -> Result<Response<AvailabilityResponse>, Status> {
+        let request = request.into_inner();
+        let slots = self.manager.availability(request).await?;
+        Ok(Response::new(AvailabilityResponse { slots }))
+    }
+    /// find the resources free for a time window, optionally reserve the best candidate
+    async fn find_available(
+        &self,
+        request: Request<FindAvailableRequest>,
+    ) -> Result<Response<FindAvailableResponse>, Status> {
+        let manager = self.manager_for(&request);
+        let request = request.into_inner();
+        if request.search.is_none() {
+            return Err(Status::invalid_argument("missing search"));
+        }
+        let (candidates, reservation) = manager.find_available(request).await?;
+        Ok(Response::new(FindAvailableResponse {
+            candidates,
+            reservation,
+        }))
     }
 }
 
@@ -151,12 +462,17 @@ impl<T> Stream for TonicReceiverStream<T> {
 mod tests {
     use super::*;
     use crate::test_utils::*;
-    use abi::Reservation;
+    use abi::{Reservation, Resource};
 
     #[tokio::test]
     async fn rpc_reserve_should_work() {
         let config = TestConfig::new();
         let service = RsvpService::from_config(&config).await.unwrap();
+        let resource = Resource::new("ocean-view-room-417", "Ocean View Room 417", "room");
+        service
+            .create_resource(tonic::Request::new(CreateResourceRequest::new(resource)))
+            .await
+            .unwrap();
         let reservation = Reservation::new_pending(
             "alice",
             "ocean-view-room-417",
@@ -164,9 +480,7 @@ mod tests {
             "2022-12-30T12:00:00-0700".parse().unwrap(),
             "Hello.",
         );
-        let request = tonic::Request::new(ReserveRequest {
-            reservation: Some(reservation.clone()),
-        });
+        let request = tonic::Request::new(ReserveRequest::new(reservation.clone()));
         let response = service.reserve(request).await.unwrap();
         let reservation1 = response.into_inner().reservation;
         assert!(reservation1.is_some());
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use abi::SinkConfig;
+use reservation::ReservationManager;
+use tokio::time;
+use tracing::error;
+
+/// delay before restarting an event sink after it fails
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// keep feeding the changes to the event sink, restart it from the acknowledged offset if it fails
+pub async fn run_sink(manager: ReservationManager, config: SinkConfig) {
+    loop {
+        let ret = match reservation::sink_from_config(&config).await {
+            Ok(sink) => manager.run_sink(&config.name, sink.as_ref()).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = ret {
+            error!("Event sink {} failed: {e:?}", config.name);
+        }
+        time::sleep(RESTART_DELAY).await;
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use reservation::WebhookDispatcher;
+use tokio::time;
+use tracing::error;
+
+/// delay before restarting a webhook dispatcher after it fails
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// keep the webhook dispatcher running, restart it from the acknowledged offset if it fails
+pub async fn run_webhook(dispatcher: WebhookDispatcher) {
+    loop {
+        if let Err(e) = dispatcher.run().await {
+            error!("Webhook dispatcher failed: {e:?}");
+        }
+        time::sleep(RESTART_DELAY).await;
+    }
+}
@@ -1,14 +1,14 @@
 use std::time::Duration;
 
 use abi::{
-    reservation_service_client::ReservationServiceClient, Config, ConfirmRequest, FilterRequest,
-    FilterResponse, QueryRequest, Reservation, ReservationFilterBuilder, ReservationQueryBuilder,
-    ReservationStatus, ReserveRequest,
+    reservation_service_client::ReservationServiceClient, CancelRequest, Config, ConfirmRequest,
+    CreateResourceRequest, DeleteResourceRequest, FilterRequest, FilterResponse,
+    GetResourceRequest, HistoryRequest, ListResourcesRequest, ListenRequest, QueryRequest,
+    Reservation, ReservationFilterBuilder, ReservationQueryBuilder, ReservationStatus,
+    ReservationUpdateType, ReserveRequest, Resource, UpdateResourceRequest,
 };
 use futures::StreamExt;
-use once_cell::sync::Lazy;
-use reservation_service::start_server;
-use sqlx::QueryBuilder;
+use reservation_service::{start_server, ACTOR_HEADER};
 use tokio::time;
 
 #[path = "../src/test_utils.rs"]
@@ -21,6 +21,7 @@ async fn grpc_server_should_work() {
     let tconfig = TestConfig::with_server_port(50001);
 
     let mut client = get_test_client(&tconfig).await;
+    make_resource(&mut client, "room").await;
 
     // first we make a reservation
     let mut rsvp = Reservation::new_pending(
@@ -132,6 +133,149 @@ async fn grpc_filter_should_work() {
     // assert_eq!(reservations.len(), filter.page_size as usize);
 }
 
+#[tokio::test]
+async fn grpc_listen_should_work() {
+    let tconfig = TestConfig::with_server_port(50004);
+    let mut client = get_test_client(&tconfig).await;
+    make_resource(&mut client, "room").await;
+
+    let mut changes = client
+        .listen(ListenRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+
+    let rsvp = Reservation::new_pending(
+        "kyros",
+        "room",
+        "2022-12-26T15:00:00-0700".parse().unwrap(),
+        "2022-12-30T12:00:00-0700".parse().unwrap(),
+        "book room",
+    );
+    let rsvp = client
+        .reserve(ReserveRequest::new(rsvp))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    let mut request = tonic::Request::new(ConfirmRequest::new(rsvp.id));
+    request
+        .metadata_mut()
+        .insert(ACTOR_HEADER, "front-desk".parse().unwrap());
+    client.confirm(request).await.unwrap();
+    client
+        .cancel(CancelRequest::new(rsvp.id).with_reason("plan changed", "kyros"))
+        .await
+        .unwrap();
+
+    // cancelled reservation is kept, so it's an update as well
+    let mut last = None;
+    for op in [
+        ReservationUpdateType::Create,
+        ReservationUpdateType::Update,
+        ReservationUpdateType::Update,
+    ] {
+        let change = changes.next().await.unwrap().unwrap();
+        assert_eq!(change.op(), op);
+        assert_eq!(change.reservation().unwrap().id, rsvp.id);
+        last = change.new;
+    }
+    let cancelled = last.unwrap();
+    assert_eq!(cancelled.status(), ReservationStatus::Cancelled);
+    assert_eq!(cancelled.cancel_reason, "plan changed");
+    assert_eq!(cancelled.cancelled_by, "kyros");
+
+    let history = client
+        .history(HistoryRequest::new(rsvp.id))
+        .await
+        .unwrap()
+        .into_inner()
+        .entries;
+    let actors: Vec<_> = history.iter().map(|entry| entry.actor.as_str()).collect();
+    assert_eq!(actors, vec!["", "front-desk", "kyros"]);
+}
+
+#[tokio::test]
+async fn grpc_resources_should_work() {
+    let tconfig = TestConfig::with_server_port(50005);
+    let mut client = get_test_client(&tconfig).await;
+
+    // reserving an unknown resource is rejected
+    let rsvp = Reservation::new_pending(
+        "kyros",
+        "room",
+        "2022-12-26T15:00:00-0700".parse().unwrap(),
+        "2022-12-30T12:00:00-0700".parse().unwrap(),
+        "book room",
+    );
+    let status = client
+        .reserve(ReserveRequest::new(rsvp.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    let mut resource = Resource::new("room", "Ocean View Room", "room");
+    resource.tags = vec!["sea-view".into()];
+    resource.timezone = "America/Los_Angeles".into();
+    let ret = client
+        .create_resource(CreateResourceRequest::new(resource.clone()))
+        .await
+        .unwrap()
+        .into_inner()
+        .resource
+        .unwrap();
+    assert_eq!(ret, resource);
+    let ret = client
+        .get_resource(GetResourceRequest::new("room"))
+        .await
+        .unwrap()
+        .into_inner()
+        .resource
+        .unwrap();
+    assert_eq!(ret, resource);
+
+    // a resource with reservations can't be deleted, only retired
+    client
+        .reserve(ReserveRequest::new(rsvp.clone()))
+        .await
+        .unwrap();
+    let status = client
+        .delete_resource(DeleteResourceRequest::new("room"))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    resource.retired = true;
+    let ret = client
+        .update_resource(UpdateResourceRequest::new("room", resource, &["retired"]))
+        .await
+        .unwrap()
+        .into_inner()
+        .resource
+        .unwrap();
+    assert!(ret.retired);
+    let status = client.reserve(ReserveRequest::new(rsvp)).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let resources = client
+        .list_resources(ListResourcesRequest::default())
+        .await
+        .unwrap()
+        .into_inner()
+        .resources;
+    assert!(resources.is_empty());
+    let resources = client
+        .list_resources(ListResourcesRequest {
+            include_retired: true,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .resources;
+    assert_eq!(resources.len(), 1);
+}
+
 async fn get_test_client(tconfig: &TestConfig) -> ReservationServiceClient<Channel> {
     let config = &tconfig.config.clone();
 
@@ -153,6 +297,7 @@ async fn setup_server(config: &Config) {
 async fn make_reservations(client: &mut ReservationServiceClient<Channel>, count: u32) {
     // we make 100 reservation without conflict
     for i in 0..count {
+        make_resource(client, &format!("router-{i}")).await;
         let mut rsvp = Reservation::new_pending(
             "alice",
             format!("router-{i}"),
@@ -173,3 +318,10 @@ async fn make_reservations(client: &mut ReservationServiceClient<Channel>, count
         assert_eq!(ret, rsvp);
     }
 }
+
+async fn make_resource(client: &mut ReservationServiceClient<Channel>, id: &str) {
+    client
+        .create_resource(CreateResourceRequest::new(Resource::new(id, id, "")))
+        .await
+        .unwrap();
+}
@@ -1,8 +1,4 @@
-use std::{
-    fmt::format,
-    path::{Path, PathBuf},
-    thread,
-};
+use std::{path::Path, thread};
 
 use sqlx::{Connection, Executor, PgConnection, PgPool};
 use tokio::runtime::Runtime;